@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use crate::linter::{read_source, Diagnostic};
+
+/// One suppressed violation. Entries are matched on `Diagnostic::fingerprint`
+/// rather than the line number, so they survive edits that shift code up or down.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct BaselineEntry {
+    path: String,
+    code: String,
+    fingerprint: String,
+}
+
+/// The set of violations that already existed when the baseline was written.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    entries: Vec<BaselineEntry>,
+}
+
+/// Builds the entry of every diagnostic, reading each file once.
+fn entries(diagnostics: &[Diagnostic]) -> Vec<BaselineEntry> {
+    let mut sources: HashMap<&str, Vec<String>> = HashMap::new();
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let lines = sources.entry(&diagnostic.path).or_insert_with(|| {
+                read_source(Path::new(&diagnostic.path))
+                    .map(|source| source.lines().map(String::from).collect())
+                    .unwrap_or_default()
+            });
+            let line = diagnostic.line.checked_sub(1).and_then(|i| lines.get(i)).map_or("", |l| l.as_str());
+            BaselineEntry {
+                path: diagnostic.path.clone(),
+                code: diagnostic.code.clone(),
+                fingerprint: diagnostic.fingerprint(line),
+            }
+        })
+        .collect()
+}
+
+impl Baseline {
+    /// Records `diagnostics` as the accepted pre-existing violations.
+    pub fn from_diagnostics(diagnostics: &[Diagnostic]) -> Self {
+        let mut entries = entries(diagnostics);
+        entries.sort_by(|a, b| (&a.path, &a.code, &a.fingerprint).cmp(&(&b.path, &b.code, &b.fingerprint)));
+        Self { entries }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        serde_json::from_str(&json).with_context(|| format!("Invalid baseline {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops the diagnostics recorded in the baseline. Each entry suppresses
+    /// one diagnostic, so a second copy of a baselined violation is reported.
+    pub fn filter(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let mut remaining: HashMap<&BaselineEntry, usize> = HashMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry).or_default() += 1;
+        }
+
+        let keep: Vec<bool> = entries(diagnostics)
+            .iter()
+            .map(|entry| match remaining.get_mut(entry) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .collect();
+        let mut keep = keep.into_iter();
+        diagnostics.retain(|_| keep.next().unwrap_or(true));
+    }
+}
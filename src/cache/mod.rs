@@ -0,0 +1,60 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::linter::Diagnostic;
+use crate::rules::{in_package, Rule};
+
+pub const CACHE_DIR_NAME: &str = ".rustlint_cache";
+
+/// On-disk store of diagnostics keyed by a hash of the file's path and
+/// content, the active rule set and the configuration, so unchanged files aren't re-linted.
+pub struct Cache {
+    dir: PathBuf,
+    settings: blake3::Hash,
+}
+
+impl Cache {
+    /// Opens (creating if needed) the cache in `dir`. Entries written under a
+    /// different config, rule set or rustlint version are never matched.
+    pub fn new(dir: PathBuf, config: &Config, rules: &[Box<dyn Rule + Sync>]) -> Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update(toml::to_string(config)?.as_bytes());
+        for rule in rules {
+            hasher.update(rule.code().as_bytes());
+            hasher.update(b"\0");
+        }
+
+        Ok(Self { dir, settings: hasher.finalize() })
+    }
+
+    /// The entry for `content` at `path`. Rules such as F401's
+    /// `allow_unused_in` depend on the path, so it is part of the key, as is
+    /// whether the file's directory is a package, which INP001 depends on.
+    fn entry(&self, content: &str, path: &Path) -> PathBuf {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(self.settings.as_bytes());
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(content.as_bytes());
+        hasher.update(&[u8::from(in_package(path))]);
+        self.dir.join(format!("{}.json", hasher.finalize().to_hex()))
+    }
+
+    /// The cached diagnostics for `content`, reported against `path`.
+    pub fn get(&self, content: &str, path: &Path) -> Option<Vec<Diagnostic>> {
+        let json = std::fs::read_to_string(self.entry(content, path)).ok()?;
+        let mut diagnostics: Vec<Diagnostic> = serde_json::from_str(&json).ok()?;
+        for diagnostic in &mut diagnostics {
+            diagnostic.path = path.to_string_lossy().to_string();
+        }
+        Some(diagnostics)
+    }
+
+    pub fn put(&self, content: &str, path: &Path, diagnostics: &[Diagnostic]) -> Result<()> {
+        std::fs::write(self.entry(content, path), serde_json::to_string(diagnostics)?)?;
+        Ok(())
+    }
+}
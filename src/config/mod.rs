@@ -1,13 +1,23 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::output::OutputFormat;
 
-#[derive(Debug, Serialize, Deserialize)]
+pub const CONFIG_FILE_NAME: &str = "rustlint.toml";
+pub const PYPROJECT_FILE_NAME: &str = "pyproject.toml";
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Config {
+    /// Oldest Python version the project supports, e.g. `"3.8"`.
+    #[serde(default)]
+    pub target_version: PythonVersion,
     #[serde(default)]
     pub rules: Rules,
     #[serde(default)]
     pub paths: Paths,
+    #[serde(default)]
+    pub output: Output,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,6 +28,56 @@ pub struct Rules {
     pub ignore_unused_variables: bool,
     #[serde(default = "default_true")]
     pub strict_pep8: bool,
+    #[serde(default = "default_max_complexity")]
+    pub max_complexity: usize,
+    #[serde(default = "default_max_function_lines")]
+    pub max_function_lines: usize,
+    #[serde(default)]
+    pub ignore_long_strings: bool,
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    #[serde(default)]
+    pub warn_global: bool,
+    #[serde(default)]
+    pub warn_print: bool,
+    #[serde(default)]
+    pub require_docstrings: bool,
+    #[serde(default)]
+    pub require_annotations: bool,
+    /// Width that space indentation must be a multiple of.
+    #[serde(default = "default_indent_size")]
+    pub indent_size: usize,
+    /// Indent with tabs: flags spaces in indentation instead of tabs.
+    #[serde(default)]
+    pub use_tabs: bool,
+    /// Path globs of files whose imports are re-exports, not flagged by `F401`.
+    #[serde(default = "default_allow_unused_in")]
+    pub allow_unused_in: Vec<String>,
+    /// Whether multi-line brackets must end their last element with a comma.
+    #[serde(default)]
+    pub trailing_comma: TrailingCommaStyle,
+    /// Variable and parameter names the `E741` rule reports as ambiguous.
+    #[serde(default = "default_ambiguous_names")]
+    pub ambiguous_names: Vec<String>,
+    /// Per-code overrides from the `[rules.severity]` table.
+    #[serde(default)]
+    pub severity: BTreeMap<String, Severity>,
+    /// Per-code switches from the `[rules.enabled]` table, turning rules on or
+    /// off regardless of their default.
+    #[serde(default)]
+    pub enabled: BTreeMap<String, bool>,
+    /// Per-code thresholds from the `[rules.escalate]` table: once a file has
+    /// at least this many warnings of a code, they are all reported as errors.
+    #[serde(default)]
+    pub escalate: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Off,
 }
 
 impl Default for Rules {
@@ -26,34 +86,222 @@ impl Default for Rules {
             max_line_length: default_max_line_length(),
             ignore_unused_variables: false,
             strict_pep8: default_true(),
+            max_complexity: default_max_complexity(),
+            max_function_lines: default_max_function_lines(),
+            ignore_long_strings: false,
+            line_ending: LineEnding::default(),
+            warn_global: false,
+            warn_print: false,
+            require_docstrings: false,
+            require_annotations: false,
+            indent_size: default_indent_size(),
+            use_tabs: false,
+            allow_unused_in: default_allow_unused_in(),
+            trailing_comma: TrailingCommaStyle::default(),
+            ambiguous_names: default_ambiguous_names(),
+            severity: BTreeMap::new(),
+            enabled: BTreeMap::new(),
+            escalate: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Default)]
+/// A `major.minor` Python version, written as a string such as `"3.8"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PythonVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl PythonVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+}
+
+impl Default for PythonVersion {
+    fn default() -> Self {
+        Self::new(3, 8)
+    }
+}
+
+impl std::str::FromStr for PythonVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parse = || {
+            let (major, minor) = s.trim().split_once('.')?;
+            Some(Self::new(major.parse().ok()?, minor.parse().ok()?))
+        };
+        parse().ok_or_else(|| anyhow::anyhow!("Invalid Python version '{}', expected e.g. \"3.8\"", s))
+    }
+}
+
+impl TryFrom<String> for PythonVersion {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl From<PythonVersion> for String {
+    fn from(version: PythonVersion) -> Self {
+        version.to_string()
+    }
+}
+
+impl std::fmt::Display for PythonVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Line ending a project expects. `Auto` accepts either, as long as a file
+/// doesn't mix them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+    #[default]
+    Auto,
+}
+
+/// Trailing comma policy for calls, definitions and collections that span
+/// several lines. `Off` leaves them alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingCommaStyle {
+    Require,
+    Forbid,
+    #[default]
+    Off,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Paths {
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// File extensions (without the dot) that are linted when walking a directory.
+    #[serde(default = "default_extensions")]
+    pub extensions: Vec<String>,
+    /// Descend into symlinked directories; symlink loops are skipped.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            exclude: Vec::new(),
+            extensions: default_extensions(),
+            follow_symlinks: false,
+        }
+    }
+}
+
+/// Reporting defaults; the matching command-line flags take precedence.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Output {
+    /// Format used when `--format` isn't given, e.g. `"json"` or `"github"`.
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+/// The parts of a `pyproject.toml` rustlint reads: its `[tool.rustlint]` table.
+#[derive(Deserialize)]
+struct PyProject {
+    tool: Option<PyProjectTools>,
+}
+
+#[derive(Deserialize)]
+struct PyProjectTools {
+    rustlint: Option<Config>,
 }
 
 impl Config {
+    /// Reads a `rustlint.toml`, or the `[tool.rustlint]` table of a
+    /// `pyproject.toml` (defaults if it has none).
     pub fn from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
+        if path.file_name().is_some_and(|name| name == PYPROJECT_FILE_NAME) {
+            let pyproject: PyProject = toml::from_str(&content)?;
+            return Ok(pyproject.tool.and_then(|tool| tool.rustlint).unwrap_or_default());
+        }
         Ok(toml::from_str(&content)?)
     }
 
-    pub fn default() -> Self {
-        Self {
-            rules: Rules::default(),
-            paths: Paths::default(),
+    /// Looks for a `rustlint.toml`, or a `pyproject.toml` with a
+    /// `[tool.rustlint]` table, in `start` and then in each of its parents.
+    /// The nearest directory wins; within one, `rustlint.toml` comes first.
+    pub fn discover(start: &Path) -> Option<PathBuf> {
+        start.ancestors().find_map(|dir| {
+            let config = dir.join(CONFIG_FILE_NAME);
+            if config.is_file() {
+                return Some(config);
+            }
+            let pyproject = dir.join(PYPROJECT_FILE_NAME);
+            has_rustlint_table(&pyproject).then_some(pyproject)
+        })
+    }
+
+    /// Loads the explicit config file if one is given, otherwise the first
+    /// config found from `start` upwards, falling back to defaults.
+    pub fn load(explicit: Option<&Path>, start: &Path) -> Result<Self> {
+        match explicit {
+            Some(path) => Self::from_file(path),
+            None => match Self::discover(start) {
+                Some(path) => Self::from_file(&path),
+                None => Ok(Self::default()),
+            },
         }
     }
 }
 
+/// Whether `path` is a readable `pyproject.toml` with a `[tool.rustlint]`
+/// table. Projects using it only for other tools are skipped by discovery.
+fn has_rustlint_table(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .is_some_and(|table| {
+            table
+                .get("tool")
+                .and_then(|tool| tool.get("rustlint"))
+                .is_some_and(toml::Value::is_table)
+        })
+}
+
 fn default_max_line_length() -> usize {
     88 // Using black's default
 }
 
+fn default_max_complexity() -> usize {
+    10
+}
+
+fn default_max_function_lines() -> usize {
+    50
+}
+
+fn default_indent_size() -> usize {
+    4
+}
+
+fn default_allow_unused_in() -> Vec<String> {
+    vec!["**/__init__.py".to_string()]
+}
+
+fn default_ambiguous_names() -> Vec<String> {
+    vec!["l".to_string(), "O".to_string(), "I".to_string()]
+}
+
+fn default_extensions() -> Vec<String> {
+    vec!["py".to_string()]
+}
+
 fn default_true() -> bool {
     true
 }
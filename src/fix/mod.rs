@@ -0,0 +1,202 @@
+use anyhow::Result;
+use rustpython_parser::parser;
+use std::path::Path;
+use crate::linter::LintContext;
+use crate::rules::Rule;
+
+/// Maximum number of lint-and-fix passes over a single file. Edits that
+/// overlap are dropped in one pass and picked up by the next.
+const MAX_FIX_PASSES: usize = 10;
+
+/// Unchanged lines shown around each change in a unified diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// A replacement of the text between two positions. Lines and columns are
+/// 1-based (columns count characters); the end position is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+    pub replacement: String,
+}
+
+impl Edit {
+    /// Removes the whole of `line`, including its line break.
+    pub fn delete_line(line: usize) -> Self {
+        Self {
+            start_line: line,
+            start_col: 1,
+            end_line: line + 1,
+            end_col: 1,
+            replacement: String::new(),
+        }
+    }
+}
+
+/// Applies the non-overlapping subset of `edits` and returns the new source
+/// along with how many edits were applied. Edits are applied from the end of
+/// the file backwards, so on overlap the one starting later (on the same line,
+/// the later column) wins and the other is skipped.
+pub fn apply_edits(source: &str, edits: &[Edit]) -> (String, usize) {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let offset = |line: usize, col: usize| -> usize {
+        let Some(&start) = line_starts.get(line.saturating_sub(1)) else {
+            return source.len();
+        };
+        let line_end = source[start..].find('\n').map_or(source.len(), |i| start + i);
+        source[start..line_end]
+            .char_indices()
+            .nth(col.saturating_sub(1))
+            .map_or(line_end, |(i, _)| start + i)
+    };
+
+    let mut sorted: Vec<&Edit> = edits.iter().collect();
+    sorted.sort_by_key(|edit| std::cmp::Reverse((edit.start_line, edit.start_col)));
+
+    let mut result = source.to_string();
+    let mut applied = 0;
+    let mut lowest_start = usize::MAX;
+    for edit in sorted {
+        let start = offset(edit.start_line, edit.start_col);
+        // Deleting a final line that has no trailing newline ends at EOF
+        let end = offset(edit.end_line, edit.end_col).max(start);
+        if end > lowest_start {
+            continue;
+        }
+        result.replace_range(start..end, &edit.replacement);
+        lowest_start = start;
+        applied += 1;
+    }
+
+    (result, applied)
+}
+
+/// Repeatedly collects and applies fixes from `rules` until the source stops
+/// changing. Returns the fixed source and the total number of edits applied.
+pub fn fix_source(source: &str, filename: &str, rules: &[Box<dyn Rule + Sync>]) -> Result<(String, usize)> {
+    let mut current = source.to_string();
+    let mut total = 0;
+
+    for _ in 0..MAX_FIX_PASSES {
+        let Ok(ast) = parser::parse_program(&current, filename) else {
+            break;
+        };
+        let context = LintContext::new(&current, &ast).with_path(Path::new(filename));
+        let mut edits = Vec::new();
+        for rule in rules {
+            if let Some(mut rule_edits) = rule.fix(&context)? {
+                edits.append(&mut rule_edits);
+            }
+        }
+        let (fixed, applied) = apply_edits(&current, &edits);
+        if applied == 0 || fixed == current {
+            break;
+        }
+        current = fixed;
+        total += applied;
+    }
+
+    Ok((current, total))
+}
+
+/// Fixes `path` in place and returns the number of edits applied.
+pub fn fix_file(path: &Path, rules: &[Box<dyn Rule + Sync>]) -> Result<usize> {
+    let source = std::fs::read_to_string(path)?;
+    let (fixed, applied) = fix_source(&source, &path.to_string_lossy(), rules)?;
+    if applied > 0 {
+        std::fs::write(path, fixed)?;
+    }
+    Ok(applied)
+}
+
+/// Computes the fixes for `path` without writing them, returning them as a
+/// unified diff (empty when nothing would change) and the number of edits.
+pub fn diff_file(path: &Path, rules: &[Box<dyn Rule + Sync>]) -> Result<(String, usize)> {
+    let source = std::fs::read_to_string(path)?;
+    let name = path.to_string_lossy();
+    let (fixed, applied) = fix_source(&source, &name, rules)?;
+    if applied == 0 {
+        return Ok((String::new(), 0));
+    }
+    Ok((unified_diff(&name, &source, &fixed), applied))
+}
+
+/// Renders the line changes from `old` to `new` as a unified diff of `path`.
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    // Keeping the line breaks makes a missing final newline a change of its own
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let lines = diff::slice(&old_lines, &new_lines);
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&i| !matches!(lines[i], diff::Result::Both(..)))
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+
+    // Group changes whose context overlaps into one hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let end = (i + DIFF_CONTEXT + 1).min(lines.len());
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.1 => hunk.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = format!("--- a/{}\n+++ b/{}\n", path, path);
+    // Old and new line numbers reached before each entry of `lines`
+    let (mut old_line, mut new_line, mut position) = (0, 0, 0);
+    for (start, end) in hunks {
+        for line in &lines[position..start] {
+            match line {
+                diff::Result::Left(_) => old_line += 1,
+                diff::Result::Right(_) => new_line += 1,
+                diff::Result::Both(..) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+            }
+        }
+        let mut body = String::new();
+        let (mut old_count, mut new_count) = (0, 0);
+        for line in &lines[start..end] {
+            match line {
+                diff::Result::Left(text) => {
+                    old_count += 1;
+                    push_line(&mut body, '-', text);
+                }
+                diff::Result::Right(text) => {
+                    new_count += 1;
+                    push_line(&mut body, '+', text);
+                }
+                diff::Result::Both(text, _) => {
+                    old_count += 1;
+                    new_count += 1;
+                    push_line(&mut body, ' ', text);
+                }
+            }
+        }
+        // An empty side is numbered after the line it follows
+        let old_start = if old_count == 0 { old_line } else { old_line + 1 };
+        let new_start = if new_count == 0 { new_line } else { new_line + 1 };
+        output.push_str(&format!("@@ -{},{} +{},{} @@\n{}", old_start, old_count, new_start, new_count, body));
+        old_line += old_count;
+        new_line += new_count;
+        position = end;
+    }
+    output
+}
+
+fn push_line(body: &mut String, sign: char, line: &str) {
+    body.push(sign);
+    body.push_str(line);
+    if !line.ends_with('\n') {
+        body.push_str("\n\\ No newline at end of file\n");
+    }
+}
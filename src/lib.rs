@@ -2,3 +2,8 @@ pub mod config;
 pub mod linter;
 pub mod rules;
 pub mod processor;
+pub mod output;
+pub mod fix;
+pub mod cache;
+pub mod baseline;
+pub mod lsp;
@@ -1,22 +1,36 @@
 use anyhow::Result;
-use rustpython_parser::{ast, parser};
-use std::{collections::HashSet, path::Path};
+use rustpython_parser::{ast, error::ParseError, parser};
+use serde::{Deserialize, Serialize};
+use regex::Regex;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use crate::config::Severity;
+use crate::rules::visitor::{self, Visitor};
 
 pub struct Linter {
     config: crate::config::Config,
+    rules: Vec<Box<dyn crate::rules::Rule + Sync>>,
 }
 
 impl Linter {
     pub fn new(config: crate::config::Config) -> Self {
-        Self { config }
+        let rules = crate::rules::get_rules(&config)
+            .into_iter()
+            .map(|r| r as Box<dyn crate::rules::Rule + Sync>)
+            .collect();
+        Self { config, rules }
     }
 
     pub fn lint_file(&self, path: &Path) -> Result<Vec<Diagnostic>> {
-        println!("Linting file: {:?}", path);
-        let content = std::fs::read_to_string(path)?;
-        println!("File content length: {}", content.len());
+        log::debug!("Linting file: {:?}", path);
+        let content = read_source(path)?;
+        log::trace!("File content length: {}", content.len());
         let mut diagnostics = self.lint_source(&content, path)?;
-        println!("Found diagnostics: {:?}", diagnostics);
+        log::trace!("Found diagnostics: {:?}", diagnostics);
         
         // Add file path to all diagnostics
         for diagnostic in &mut diagnostics {
@@ -27,240 +41,291 @@ impl Linter {
     }
 
     pub fn lint_source(&self, source: &str, path: &Path) -> Result<Vec<Diagnostic>> {
-        let mut diagnostics = Vec::new();
+        let (ast, syntax_error) = parse(source, path);
+        let context = LintContext::new(source, &ast).with_path(path);
 
-        // Always run style checks since they don't depend on valid syntax
-        self.check_style(source, &mut diagnostics)?;
-
-        // Try parsing the file for syntax and unused code checks
-        match parser::parse_program(source, path.to_str().unwrap_or("unknown")) {
-            Ok(ast) => {
-                self.check_syntax(&ast, &mut diagnostics)?;
-                self.check_unused(&ast, &mut diagnostics)?;
-            }
-            Err(e) => {
-                // Handle syntax errors
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Error,
-                    message: format!("Syntax error: {}", e),
-                    line: 1, // TODO: Extract line number from error
-                    column: 1,
-                    path: String::new(),
-                });
-            }
-        }
+        // The rules are the single source of truth for every check, including
+        // indentation and syntax errors
+        let mut diagnostics = lint_context(&context, syntax_error, &self.rules, None)?;
 
+        apply_severity(&mut diagnostics, &self.config.rules.severity);
+        apply_escalation(&mut diagnostics, &self.config.rules.escalate);
         Ok(diagnostics)
     }
+}
 
-    fn check_syntax(&self, _ast: &ast::Suite, _diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
-        // Basic syntax validation is handled by rustpython-parser
-        // We'll add more advanced checks later
-        Ok(())
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+    Info,
+}
+
+impl std::fmt::Display for DiagnosticLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Info => "info",
+        };
+        f.write_str(name)
     }
+}
 
-    fn check_style(&self, source: &str, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
-        let lines: Vec<&str> = source.lines().collect();
-        let max_length = self.config.rules.max_line_length;
-
-        for (i, &line) in lines.iter().enumerate() {
-            let line_num = i + 1;
-            
-            // Check line length
-            let line_length = line.chars().count(); // Use char count instead of byte length
-            println!("Line {}: length = {}, max = {}", line_num, line_length, max_length); // Debug print
-            if line_length > max_length {
-                println!("Found long line: {}", line); // Debug print
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Warning,
-                    message: format!(
-                        "Line too long ({} > {})",
-                        line_length,
-                        max_length
-                    ),
-                    line: line_num,
-                    column: max_length + 1,
-                    path: String::new(),
-                });
-            }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    /// Last line and column (1-based, inclusive) of the flagged range. Checks
+    /// that only know a point set these to `line`/`column`.
+    pub end_line: usize,
+    pub end_column: usize,
+    pub path: String,
+    pub code: String,
+}
 
-            // Check indentation
-            let indent_size = line.chars()
-                .take_while(|&c| c == ' ')
-                .count();
-            
-            if indent_size > 0 && indent_size % 4 != 0 && !line.trim().is_empty() {
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Warning,
-                    message: format!("Indentation of {} spaces should be a multiple of 4", indent_size),
-                    line: line_num,
-                    column: 1,
-                    path: String::new(),
-                });
-            }
+/// A number standing on its own in a message, not part of a name like `x1`.
+fn message_numbers() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b\d+\b").unwrap())
+}
 
-            // Check for mixed tabs and spaces
-            if line.contains('\t') {
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Warning,
-                    message: "Line contains tabs (use spaces instead)".to_string(),
-                    line: line_num,
-                    column: line.find('\t').unwrap_or(0) + 1,
-                    path: String::new(),
-                });
-            }
+impl Diagnostic {
+    /// Stable identity of the reported problem, for correlating results across
+    /// runs. Hashes the code, message template, path and `context` (the flagged
+    /// source line, whitespace-normalized) but not the line number, so it
+    /// survives edits elsewhere in the file. Standalone numbers in the message,
+    /// such as `previous definition on line 4`, are masked for the same reason.
+    pub fn fingerprint(&self, context: &str) -> String {
+        let normalized = context.split_whitespace().collect::<Vec<_>>().join(" ");
+        let template = message_numbers().replace_all(&self.message, "#");
+        let mut hasher = blake3::Hasher::new();
+        for part in [&self.code, template.as_ref(), &self.path, &normalized] {
+            hasher.update(part.as_bytes());
+            hasher.update(b"\0");
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+}
 
-            // Check trailing whitespace
-            if line.trim_end().len() < line.len() {
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Warning,
-                    message: "Trailing whitespace".to_string(),
-                    line: line_num,
-                    column: line.trim_end().len() + 1,
-                    path: String::new(),
-                });
-            }
+/// Everything the rules get to see about one file: its source, split into
+/// lines once, and its syntax tree, parsed once. A file that failed to parse
+/// has an empty tree, so only line-based rules find anything.
+pub struct LintContext<'a> {
+    pub source: &'a str,
+    pub path: &'a Path,
+    pub ast: &'a ast::Suite,
+    pub lines: Vec<&'a str>,
+    string_ranges: OnceCell<Vec<(usize, usize)>>,
+}
+
+impl<'a> LintContext<'a> {
+    pub fn new(source: &'a str, ast: &'a ast::Suite) -> Self {
+        Self {
+            source,
+            path: Path::new(""),
+            ast,
+            lines: source.lines().collect(),
+            string_ranges: OnceCell::new(),
         }
+    }
 
-        Ok(())
+    /// The file being linted; empty unless set.
+    pub fn with_path(mut self, path: &'a Path) -> Self {
+        self.path = path;
+        self
     }
 
-    fn check_unused(&self, ast: &ast::Suite, diagnostics: &mut Vec<Diagnostic>) -> Result<()> {
-        let mut defined_imports = HashSet::new();
-        let mut used_names = HashSet::new();
-
-        // First pass: collect all imports and defined names
-        for stmt in ast.iter() {
-            match &stmt.node {
-                ast::StmtKind::Import { names } => {
-                    for alias in names {
-                        defined_imports.insert((
-                            alias.node.name.to_string(),
-                            stmt.location.row(),
-                            stmt.location.column(),
-                        ));
-                    }
-                }
-                ast::StmtKind::ImportFrom { names, .. } => {
-                    for alias in names {
-                        defined_imports.insert((
-                            alias.node.name.to_string(),
-                            stmt.location.row(),
-                            stmt.location.column(),
-                        ));
-                    }
-                }
-                _ => {}
-            }
-        }
+    /// The first and last line (1-based, inclusive) of every string literal,
+    /// including f-strings, in source order. Collected on first use.
+    pub fn string_ranges(&self) -> &[(usize, usize)] {
+        self.string_ranges.get_or_init(|| {
+            let mut strings = StringRanges::default();
+            visitor::walk_suite(&mut strings, self.ast);
+            strings.ranges
+        })
+    }
+}
 
-        // Second pass: collect all used names
-        self.collect_used_names_from_suite(ast, &mut used_names);
-
-        // Report unused imports
-        for (import, line, col) in defined_imports {
-            if !used_names.contains(&import) {
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Warning,
-                    message: format!("Unused import '{}'", import),
-                    line,
-                    column: col,
-                    path: String::new(),
-                });
+#[derive(Default)]
+struct StringRanges {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl Visitor for StringRanges {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match &expr.node {
+            ast::ExprKind::Constant { value: ast::Constant::Str(_), .. } | ast::ExprKind::JoinedStr { .. } => {
+                let start = expr.location.row();
+                self.ranges.push((start, expr.end_location.map_or(start, |end| end.row())));
             }
+            _ => visitor::walk_expr(self, expr),
         }
+    }
+}
 
-        Ok(())
+/// Builds the diagnostic for a parse failure, located where the parser gave up.
+fn syntax_error(error: &ParseError) -> Diagnostic {
+    let line = error.location.row().max(1);
+    // Parser error columns are already 1-based, except at end of input
+    let column = error.location.column().max(1);
+    Diagnostic {
+        level: DiagnosticLevel::Error,
+        message: format!("Syntax error: {}", error.error),
+        line,
+        column,
+        end_line: line,
+        end_column: column,
+        path: String::new(),
+        code: "E999".to_string(),
     }
+}
 
-    fn collect_used_names_from_suite(&self, suite: &ast::Suite, used_names: &mut HashSet<String>) {
-        for stmt in suite {
-            match &stmt.node {
-                ast::StmtKind::Expr { value } => {
-                    self.collect_used_names(value, used_names);
-                }
-                ast::StmtKind::FunctionDef { body, .. } => {
-                    for stmt in body {
-                        if let ast::StmtKind::Expr { value } = &stmt.node {
-                            self.collect_used_names(value, used_names);
-                        }
-                    }
-                }
-                ast::StmtKind::Assign { targets, value, .. } => {
-                    for target in targets {
-                        self.collect_used_names(target, used_names);
-                    }
-                    self.collect_used_names(value, used_names);
-                }
-                _ => {}
-            }
-        }
+/// Reclassifies diagnostics according to the `[rules.severity]` overrides,
+/// dropping those whose code is turned `off`.
+pub fn apply_severity(diagnostics: &mut Vec<Diagnostic>, overrides: &BTreeMap<String, Severity>) {
+    if overrides.is_empty() {
+        return;
     }
+    diagnostics.retain_mut(|diagnostic| match overrides.get(&diagnostic.code) {
+        Some(Severity::Off) => false,
+        Some(Severity::Error) => {
+            diagnostic.level = DiagnosticLevel::Error;
+            true
+        }
+        Some(Severity::Warning) => {
+            diagnostic.level = DiagnosticLevel::Warning;
+            true
+        }
+        None => true,
+    });
+}
 
-    fn collect_used_names(&self, node: &ast::Expr, used_names: &mut HashSet<String>) {
-        match &node.node {
-            ast::ExprKind::Name { id, .. } => {
-                used_names.insert(id.to_string());
-            }
-            ast::ExprKind::Call { func, args, .. } => {
-                self.collect_used_names(func, used_names);
-                for arg in args {
-                    self.collect_used_names(arg, used_names);
-                }
-            }
-            ast::ExprKind::Attribute { value, attr, .. } => {
-                used_names.insert(attr.to_string());
-                self.collect_used_names(value, used_names);
-            }
-            _ => {}
+/// Raises warnings to errors per the `[rules.escalate]` thresholds, counting
+/// each code separately in each file. Runs after [`apply_severity`], so
+/// diagnostics turned `off` don't count.
+pub fn apply_escalation(diagnostics: &mut [Diagnostic], thresholds: &BTreeMap<String, usize>) {
+    if thresholds.is_empty() {
+        return;
+    }
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    for diagnostic in diagnostics.iter() {
+        if diagnostic.level == DiagnosticLevel::Warning && thresholds.contains_key(&diagnostic.code) {
+            *counts.entry((diagnostic.path.clone(), diagnostic.code.clone())).or_default() += 1;
+        }
+    }
+    for diagnostic in diagnostics.iter_mut() {
+        let Some(&threshold) = thresholds.get(&diagnostic.code) else {
+            continue;
+        };
+        let count = counts.get(&(diagnostic.path.clone(), diagnostic.code.clone())).copied().unwrap_or(0);
+        if diagnostic.level == DiagnosticLevel::Warning && count >= threshold {
+            diagnostic.level = DiagnosticLevel::Error;
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub enum DiagnosticLevel {
-    Error,
-    Warning,
+pub fn lint_file(path: &Path, rules: &[Box<dyn crate::rules::Rule + Sync>]) -> Result<Vec<Diagnostic>> {
+    let content = read_source(path)?;
+    lint_content(&content, path, rules)
 }
 
-#[derive(Debug)]
-pub struct Diagnostic {
-    pub level: DiagnosticLevel,
-    pub message: String,
-    pub line: usize,
-    pub column: usize,
-    pub path: String,
+/// Reads a file as raw bytes, keeping any BOM and replacing invalid UTF-8
+/// rather than failing, so encoding problems are reported as diagnostics.
+pub fn read_source(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
 }
 
-pub fn lint_file(path: &Path, rules: &[Box<dyn crate::rules::Rule + Sync>]) -> Result<Vec<Diagnostic>> {
-    let content = std::fs::read_to_string(path)?;
-    let mut diagnostics = Vec::new();
-
-    // Try parsing the file
-    match parser::parse_program(&content, path.to_str().unwrap_or("unknown")) {
-        Ok(ast) => {
-            // Apply each rule
-            for rule in rules {
-                let mut rule_diagnostics = rule.check(&ast, &content)?;
-                diagnostics.append(&mut rule_diagnostics);
-            }
-        }
-        Err(e) => {
-            // Handle syntax errors
-            diagnostics.push(Diagnostic {
-                level: DiagnosticLevel::Error,
-                message: format!("Syntax error: {}", e),
-                line: 1,
-                column: 1,
-                path: String::new(),
-            });
+/// Lints an in-memory buffer through the rule pipeline, reporting `filename`
+/// as the path of every diagnostic. Nothing is read from or written to disk.
+pub fn lint_str(source: &str, filename: &str, rules: &[Box<dyn crate::rules::Rule + Sync>]) -> Result<Vec<Diagnostic>> {
+    lint_content(source, Path::new(filename), rules)
+}
+
+/// Runs `rules` over source that is already in memory, reporting `path` as
+/// the location of every diagnostic.
+pub fn lint_content(content: &str, path: &Path, rules: &[Box<dyn crate::rules::Rule + Sync>]) -> Result<Vec<Diagnostic>> {
+    lint_content_timed(content, path, rules, None)
+}
+
+/// Like [`lint_content`], adding the time each rule takes to `timings`.
+pub fn lint_content_timed(
+    content: &str,
+    path: &Path,
+    rules: &[Box<dyn crate::rules::Rule + Sync>],
+    timings: Option<&RuleTimings>,
+) -> Result<Vec<Diagnostic>> {
+    let (ast, syntax_error) = parse(content, path);
+    let context = LintContext::new(content, &ast).with_path(path);
+    lint_context(&context, syntax_error, rules, timings)
+}
+
+/// Parses `content`. On a syntax error the tree is empty and the error is
+/// returned as a diagnostic alongside it.
+fn parse(content: &str, path: &Path) -> (ast::Suite, Option<Diagnostic>) {
+    match parser::parse_program(content, path.to_str().unwrap_or("unknown")) {
+        Ok(ast) => (ast, None),
+        Err(e) => (Vec::new(), Some(syntax_error(&e))),
+    }
+}
+
+/// Runs every rule against the shared `context`, reporting its path as the
+/// location of every diagnostic.
+fn lint_context(
+    context: &LintContext,
+    syntax_error: Option<Diagnostic>,
+    rules: &[Box<dyn crate::rules::Rule + Sync>],
+    timings: Option<&RuleTimings>,
+) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics: Vec<Diagnostic> = syntax_error.into_iter().collect();
+
+    // Apply each rule
+    for (index, rule) in rules.iter().enumerate() {
+        let start = std::time::Instant::now();
+        let mut rule_diagnostics = rule.check(context)?;
+        if let Some(timings) = timings {
+            timings.record(index, start.elapsed());
         }
+        diagnostics.append(&mut rule_diagnostics);
     }
 
     // Add file path to all diagnostics
     for diagnostic in &mut diagnostics {
-        diagnostic.path = path.to_string_lossy().to_string();
+        diagnostic.path = context.path.to_string_lossy().to_string();
     }
 
     Ok(diagnostics)
 }
+
+/// Wall time spent in each rule, summed across files. Indexed like the rule
+/// list it was created for, and safe to update from parallel workers.
+pub struct RuleTimings {
+    nanos: Vec<AtomicU64>,
+}
+
+impl RuleTimings {
+    pub fn new(rule_count: usize) -> Self {
+        Self { nanos: (0..rule_count).map(|_| AtomicU64::new(0)).collect() }
+    }
+
+    /// Adds `elapsed` to the total of the rule at `index`.
+    pub fn record(&self, index: usize, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.nanos[index].fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// The total per rule as `(code, name, time)`, slowest first.
+    pub fn report(&self, rules: &[Box<dyn crate::rules::Rule + Sync>]) -> Vec<(&'static str, &'static str, Duration)> {
+        let mut report: Vec<_> = rules
+            .iter()
+            .zip(&self.nanos)
+            .map(|(rule, nanos)| (rule.code(), rule.name(), Duration::from_nanos(nanos.load(Ordering::Relaxed))))
+            .collect();
+        report.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+        report
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::collections::BTreeMap;
+use crate::config::Severity;
+use crate::linter::{apply_escalation, apply_severity, lint_str, Diagnostic, DiagnosticLevel};
+use crate::rules::Rule;
+
+/// JSON-RPC error code for requests the server doesn't implement.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Reads one `Content-Length`-framed JSON-RPC message, or `None` at end of input.
+fn read_message<R: BufRead>(input: &mut R) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(length) = header.strip_prefix("Content-Length:") {
+            content_length = Some(length.trim().parse::<usize>()?);
+        }
+    }
+
+    let length = content_length.ok_or_else(|| anyhow!("LSP message without a Content-Length header"))?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn write_message<W: Write>(output: &mut W, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()?;
+    Ok(())
+}
+
+/// Converts a diagnostic to an LSP `Diagnostic`. LSP positions are 0-based and
+/// range ends are exclusive, while ours are 1-based and inclusive.
+pub fn to_lsp_diagnostic(diagnostic: &Diagnostic) -> Value {
+    let start_line = diagnostic.line.saturating_sub(1);
+    let start_character = diagnostic.column.saturating_sub(1);
+    let (end_line, end_character) = if diagnostic.end_line > diagnostic.line {
+        (diagnostic.end_line - 1, diagnostic.end_column)
+    } else {
+        (start_line, diagnostic.end_column.max(diagnostic.column))
+    };
+    let severity = match diagnostic.level {
+        DiagnosticLevel::Error => 1,
+        DiagnosticLevel::Warning => 2,
+        DiagnosticLevel::Info => 3,
+    };
+
+    json!({
+        "range": {
+            "start": { "line": start_line, "character": start_character },
+            "end": { "line": end_line, "character": end_character },
+        },
+        "severity": severity,
+        "code": diagnostic.code,
+        "source": "rustlint",
+        "message": diagnostic.message,
+    })
+}
+
+/// Lints `text` and builds the `textDocument/publishDiagnostics` notification for `uri`.
+fn publish(
+    uri: &str,
+    text: &str,
+    rules: &[Box<dyn Rule + Sync>],
+    severity: &BTreeMap<String, Severity>,
+    escalate: &BTreeMap<String, usize>,
+) -> Result<Value> {
+    let filename = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut diagnostics = lint_str(text, filename, rules)?;
+    apply_severity(&mut diagnostics, severity);
+    apply_escalation(&mut diagnostics, escalate);
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+            "uri": uri,
+            "diagnostics": diagnostics.iter().map(to_lsp_diagnostic).collect::<Vec<_>>(),
+        },
+    }))
+}
+
+/// Serves the Language Server Protocol over `input`/`output` until the client
+/// sends `exit` or closes the stream. Documents are synced in full and linted
+/// in memory whenever they are opened or changed, with `severity` overrides
+/// and `escalate` thresholds applied as on the command line.
+pub fn run<R: BufRead, W: Write>(
+    mut input: R,
+    mut output: W,
+    rules: &[Box<dyn Rule + Sync>],
+    severity: &BTreeMap<String, Severity>,
+    escalate: &BTreeMap<String, usize>,
+) -> Result<()> {
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or("");
+        let id = message.get("id").cloned();
+        let params = &message["params"];
+
+        match method {
+            "initialize" => {
+                let result = json!({
+                    "capabilities": { "textDocumentSync": 1 },
+                    "serverInfo": { "name": "rustlint", "version": env!("CARGO_PKG_VERSION") },
+                });
+                write_message(&mut output, &json!({ "jsonrpc": "2.0", "id": id, "result": result }))?;
+            }
+            "shutdown" => {
+                write_message(&mut output, &json!({ "jsonrpc": "2.0", "id": id, "result": null }))?;
+            }
+            "exit" => return Ok(()),
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                let uri = document["uri"].as_str().unwrap_or_default();
+                let text = document["text"].as_str().unwrap_or_default();
+                write_message(&mut output, &publish(uri, text, rules, severity, escalate)?)?;
+            }
+            "textDocument/didChange" => {
+                // With full sync the last change holds the whole document
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                if let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                {
+                    write_message(&mut output, &publish(uri, text, rules, severity, escalate)?)?;
+                }
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                let cleared = json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": uri, "diagnostics": [] },
+                });
+                write_message(&mut output, &cleared)?;
+            }
+            _ => {
+                // Notifications we don't handle are ignored; requests get an error
+                if let Some(id) = id {
+                    let error = json!({ "code": METHOD_NOT_FOUND, "message": format!("Unhandled method {}", method) });
+                    write_message(&mut output, &json!({ "jsonrpc": "2.0", "id": id, "error": error }))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
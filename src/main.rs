@@ -1,65 +1,359 @@
-mod config;
-mod linter;
-mod rules;
-mod processor;
-
-use std::path::PathBuf;
-use clap::Parser;
+use std::collections::HashMap;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use clap::{Parser, Subcommand};
 use anyhow::Result;
 use colored::*;
-use crate::rules::Rule;
-use env_logger;
+use rustlint::baseline::Baseline;
+use rustlint::cache::{Cache, CACHE_DIR_NAME};
+use rustlint::config::Config;
+use rustlint::rules::{self, RuleRegistry};
+use rustlint::output::{self, ColorChoice, OutputFormat};
+use rustlint::{fix, linter, lsp, processor};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(help = "Files or directories to lint")]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(help = "Files or directories to lint (use - to read from stdin)")]
     paths: Vec<PathBuf>,
+
+    #[arg(long, value_name = "PATH", help = "Path to a rustlint.toml (overrides auto-discovery)")]
+    config: Option<PathBuf>,
+
+    #[arg(long, value_name = "CODE", help = "Print the description of a rule and exit")]
+    explain: Option<String>,
+
+    #[arg(long, value_name = "CODES", value_delimiter = ',', help = "Only run rules with these codes (comma-separated)")]
+    select: Vec<String>,
+
+    #[arg(long, value_name = "CODES", value_delimiter = ',', help = "Skip rules with these codes (comma-separated)")]
+    ignore: Vec<String>,
+
+    #[arg(long = "extension", value_name = "EXT", help = "Also lint files with this extension when walking directories, e.g. pyi (repeatable)")]
+    extensions: Vec<String>,
+
+    #[arg(long, help = "Descend into symlinked directories when walking directories")]
+    follow_symlinks: bool,
+
+    #[arg(long, value_enum, help = "Output format [default: output.format from the config, or text]")]
+    format: Option<OutputFormat>,
+
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, help = "When to color text output (NO_COLOR disables auto)")]
+    color: ColorChoice,
+
+    #[arg(long, help = "Apply automatic fixes and write the files back")]
+    fix: bool,
+
+    #[arg(long, value_name = "CODES", value_delimiter = ',', help = "Apply automatic fixes for these codes only (comma-separated; implies --fix)")]
+    fix_only: Vec<String>,
+
+    #[arg(long, help = "Print the automatic fixes as a unified diff and exit, without writing any files (overrides --fix)")]
+    diff: bool,
+
+    #[arg(long, value_name = "NAME", default_value = "<stdin>", help = "Filename to report for source read from stdin")]
+    stdin_filename: String,
+
+    #[arg(long, short = 'j', value_name = "N", default_value_t = 0, help = "Number of files to lint in parallel (0 uses all logical CPUs)")]
+    jobs: usize,
+
+    #[arg(long, help = "Lint every file from scratch instead of using the .rustlint_cache directory")]
+    no_cache: bool,
+
+    #[arg(long, help = "Print the offending source line with a caret under each diagnostic")]
+    show_source: bool,
+
+    #[arg(long, help = "Print text diagnostics under a header for each file")]
+    group_by_file: bool,
+
+    #[arg(long, value_name = "N", default_value_t = 0, help = "Print at most N text diagnostics, earliest first (0 prints all)")]
+    max_diagnostics: usize,
+
+    #[arg(long, help = "Print the time spent in each rule to stderr, slowest first (disables the cache)")]
+    timing: bool,
+
+    #[arg(long, help = "Print the number of diagnostics per rule code, most frequent first")]
+    statistics: bool,
+
+    #[arg(long, help = "Show a progress bar on stderr while linting (only when stderr is a terminal)")]
+    progress: bool,
+
+    #[arg(long, short, help = "Only print error-level diagnostics; no summary, statistics or progress")]
+    quiet: bool,
+
+    #[arg(long, short, conflicts_with = "quiet", help = "Log each file as it is linted (debug-level logging)")]
+    verbose: bool,
+
+    #[arg(long, value_name = "FILE", help = "Suppress diagnostics recorded in this baseline file")]
+    baseline: Option<PathBuf>,
+
+    #[arg(long, requires = "baseline", help = "Record the current diagnostics in the --baseline file and exit")]
+    write_baseline: bool,
+
+    #[arg(long, help = "Exit with status 1 on warnings as well as errors")]
+    error_on_warning: bool,
+
+    #[arg(long, help = "Always exit with status 0, even if diagnostics are found")]
+    exit_zero: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run a Language Server Protocol server over stdio
+    Lsp,
 }
 
 fn main() -> Result<()> {
-    env_logger::init();
     let args = Args::parse();
-    
+
+    // RUST_LOG still applies; --verbose only raises the default level
+    let mut logger = env_logger::Builder::from_default_env();
+    if args.verbose {
+        logger.filter_level(log::LevelFilter::Debug);
+    }
+    logger.init();
+
+    // Any non-empty NO_COLOR counts, per https://no-color.org
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    colored::control::set_override(args.color.should_color(no_color, std::io::stdout().is_terminal()));
+
+    if let Some(code) = &args.explain {
+        let rule = rules::list_rules()
+            .into_iter()
+            .find(|rule| rule.code.eq_ignore_ascii_case(code))
+            .ok_or_else(|| anyhow::anyhow!("Unknown rule code '{}'", code))?;
+        println!("{} ({})\n\n{}", rule.code, rule.name, rule.description);
+        return Ok(());
+    }
+
+    if let Some(Command::Lsp) = args.command {
+        let config = Config::load(args.config.as_deref(), &std::env::current_dir()?)?;
+        let rules = RuleRegistry::from_config(&config).filter(&args.select, &args.ignore);
+        return lsp::run(std::io::stdin().lock(), std::io::stdout().lock(), &rules, &config.rules.severity, &config.rules.escalate);
+    }
+
     if args.paths.is_empty() {
         println!("No files specified. Use --help for usage information.");
         return Ok(());
     }
 
-    let rules: Vec<Box<dyn Rule + Sync>> = rules::get_default_rules()
-        .into_iter()
-        .map(|r| r as Box<dyn Rule + Sync>)
-        .collect();
+    let mut config = Config::load(args.config.as_deref(), &std::env::current_dir()?)?;
+    for extension in &args.extensions {
+        let extension = extension.trim_start_matches('.').to_string();
+        if !config.paths.extensions.contains(&extension) {
+            config.paths.extensions.push(extension);
+        }
+    }
+
+    if args.follow_symlinks {
+        config.paths.follow_symlinks = true;
+    }
+
+    let rules = RuleRegistry::from_config(&config).filter(&args.select, &args.ignore);
 
     let mut all_files = Vec::new();
+    let mut read_stdin = false;
 
     // Collect all Python files from the specified paths
     for path in args.paths {
-        if path.is_dir() {
-            let mut python_files = processor::find_python_files(&path)?;
+        if path.as_os_str() == "-" {
+            read_stdin = true;
+        } else if path.is_dir() {
+            let mut python_files = processor::find_python_files_with_config(&path, &config.paths)?;
             all_files.append(&mut python_files);
         } else if path.is_file() {
             all_files.push(path);
         }
     }
 
+    // --fix-only narrows the fixes; every selected rule still reports
+    let fix_rules = RuleRegistry::from_config(&config)
+        .filter(&args.select, &args.ignore)
+        .filter(&args.fix_only, &[]);
+
+    // Preview only: nothing is linted or written, and pending fixes fail the run
+    if args.diff {
+        let mut fixes_pending = 0;
+        let mut files_changed = 0;
+        for file in &all_files {
+            let (diff, pending) = fix::diff_file(file, &fix_rules)?;
+            if pending > 0 {
+                print!("{}", diff);
+                fixes_pending += pending;
+                files_changed += 1;
+            }
+        }
+        if !args.quiet {
+            eprintln!("Would apply {} fixes in {} files", fixes_pending, files_changed);
+        }
+        if files_changed > 0 && !args.exit_zero {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.fix || !args.fix_only.is_empty() {
+        let mut fixes_applied = 0;
+        let mut files_fixed = 0;
+        for file in &all_files {
+            let applied = fix::fix_file(file, &fix_rules)?;
+            if applied > 0 {
+                fixes_applied += applied;
+                files_fixed += 1;
+            }
+        }
+        // Reported on stderr so machine-readable formats stay parseable
+        if !args.quiet {
+            eprintln!("Applied {} fixes in {} files", fixes_applied, files_fixed);
+        }
+    }
+
     // Process files in parallel
-    let diagnostics = processor::process_files(all_files, &rules)?;
-
-    // Print diagnostics
-    for diagnostic in diagnostics {
-        let level_str = match diagnostic.level {
-            linter::DiagnosticLevel::Error => "error".red(),
-            linter::DiagnosticLevel::Warning => "warning".yellow(),
-        };
-
-        println!(
-            "{}: {} at {}:{}",
-            level_str,
-            diagnostic.message,
-            diagnostic.path,
-            diagnostic.line
-        );
+    // Cached files skip the rules entirely, which would skew the timings
+    let cache = if args.no_cache || args.timing {
+        None
+    } else {
+        Some(Cache::new(std::env::current_dir()?.join(CACHE_DIR_NAME), &config, &rules)?)
+    };
+    let pool = processor::thread_pool(args.jobs)?;
+    let mut linted: Vec<String> = all_files.iter().map(|file| file.to_string_lossy().to_string()).collect();
+    let timings = args.timing.then(|| linter::RuleTimings::new(rules.len()));
+    let progress = (args.progress && !args.quiet && std::io::stderr().is_terminal())
+        .then(|| output::ProgressBar::new(all_files.len()));
+    let options = processor::ProcessOptions { cache: cache.as_ref(), timings: timings.as_ref(), progress: progress.as_ref() };
+    let mut diagnostics = pool.install(|| processor::process_files_with_options(all_files, &rules, &options))?;
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
+
+    // Source of each linted file, loaded on demand for --show-source
+    let mut sources: HashMap<String, Vec<String>> = HashMap::new();
+
+    // Source piped in with `-` is linted but never fixed in place
+    if read_stdin {
+        let mut source = String::new();
+        std::io::stdin().read_to_string(&mut source)?;
+        let path = Path::new(&args.stdin_filename);
+        diagnostics.extend(linter::lint_content_timed(&source, path, &rules, timings.as_ref())?);
+        sources.insert(args.stdin_filename.clone(), source.lines().map(String::from).collect());
+        linted.push(args.stdin_filename.clone());
+    }
+
+    // On stderr, like the fix summary, so machine-readable formats stay parseable
+    if let Some(timings) = &timings {
+        for (code, name, elapsed) in timings.report(&rules) {
+            eprintln!("{} ({}): {}ms", code, name, elapsed.as_millis());
+        }
+    }
+
+    linter::apply_severity(&mut diagnostics, &config.rules.severity);
+    linter::apply_escalation(&mut diagnostics, &config.rules.escalate);
+
+    if let Some(path) = &args.baseline {
+        if args.write_baseline {
+            let baseline = Baseline::from_diagnostics(&diagnostics);
+            baseline.save(path)?;
+            eprintln!("Wrote {} diagnostics to baseline {}", baseline.len(), path.display());
+            return Ok(());
+        }
+        Baseline::load(path)?.filter(&mut diagnostics);
+    }
+
+    output::sort_diagnostics(&mut diagnostics);
+
+    // --exit-zero takes precedence; otherwise errors always fail the run and
+    // warnings only do so with --error-on-warning
+    let failed = !args.exit_zero
+        && diagnostics.iter().any(|d| match d.level {
+            linter::DiagnosticLevel::Error => true,
+            linter::DiagnosticLevel::Warning => args.error_on_warning,
+            linter::DiagnosticLevel::Info => false,
+        });
+
+    // Warnings still count towards the exit status, they just aren't printed
+    if args.quiet {
+        diagnostics.retain(|d| d.level == linter::DiagnosticLevel::Error);
+    }
+
+    match args.format.unwrap_or(config.output.format) {
+        OutputFormat::Json => {
+            println!("{}", output::to_json(&diagnostics)?);
+        }
+        OutputFormat::Sarif => {
+            println!("{}", output::to_sarif(&diagnostics, &rules, &std::env::current_dir()?)?);
+        }
+        OutputFormat::Junit => {
+            println!("{}", output::to_junit(&diagnostics, &linted));
+        }
+        OutputFormat::Github => {
+            if !diagnostics.is_empty() {
+                println!("{}", output::to_github(&diagnostics));
+            }
+        }
+        OutputFormat::Text => {
+            // Print diagnostics, capped by --max-diagnostics; they're already sorted
+            let limit = if args.max_diagnostics == 0 { diagnostics.len() } else { args.max_diagnostics };
+            let mut current_path: Option<&str> = None;
+            for diagnostic in diagnostics.iter().take(limit) {
+                let level_str = match diagnostic.level {
+                    linter::DiagnosticLevel::Error => "error".red(),
+                    linter::DiagnosticLevel::Warning => "warning".yellow(),
+                    linter::DiagnosticLevel::Info => "info".cyan(),
+                };
+
+                if args.group_by_file {
+                    if current_path != Some(diagnostic.path.as_str()) {
+                        println!("{}", diagnostic.path.bold());
+                        current_path = Some(&diagnostic.path);
+                    }
+                    println!(
+                        "  {}:{}: {}: {}",
+                        diagnostic.line,
+                        diagnostic.column,
+                        level_str,
+                        diagnostic.message
+                    );
+                } else {
+                    println!(
+                        "{}: {} at {}:{}",
+                        level_str,
+                        diagnostic.message,
+                        diagnostic.path,
+                        diagnostic.line
+                    );
+                }
+
+                if args.show_source {
+                    let lines = sources.entry(diagnostic.path.clone()).or_insert_with(|| {
+                        linter::read_source(Path::new(&diagnostic.path))
+                            .map(|source| source.lines().map(String::from).collect())
+                            .unwrap_or_default()
+                    });
+                    if let Some(line) = diagnostic.line.checked_sub(1).and_then(|i| lines.get(i)) {
+                        println!("{}", output::render_snippet(line, diagnostic.column));
+                    }
+                }
+            }
+
+            if diagnostics.len() > limit {
+                println!("... and {} more (use --max-diagnostics 0 for all)", diagnostics.len() - limit);
+            }
+
+            if !args.quiet {
+                if args.statistics {
+                    for (code, count) in output::statistics(&diagnostics) {
+                        println!("{:>6}  {}", count, code);
+                    }
+                }
+                println!("{}", output::summary(&diagnostics).bold());
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
     }
 
     Ok(())
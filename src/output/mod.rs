@@ -0,0 +1,291 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use crate::linter::{Diagnostic, DiagnosticLevel};
+use crate::rules::Rule;
+
+mod progress;
+pub use progress::{render_progress, ProgressBar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable, colored output
+    #[default]
+    Text,
+    /// A JSON array of diagnostics
+    Json,
+    /// A SARIF 2.1.0 log, e.g. for GitHub code scanning
+    Sarif,
+    /// GitHub Actions workflow commands, shown as inline annotations on pull requests
+    Github,
+    /// A JUnit XML report with one test case per file, e.g. for Jenkins or GitLab
+    Junit,
+}
+
+/// When to color text output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` isn't set
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to emit ANSI colors. An explicit `always`/`never` wins over
+    /// `NO_COLOR`, which in turn wins over terminal detection.
+    pub fn should_color(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && is_terminal,
+        }
+    }
+}
+
+/// Orders diagnostics by file, then top to bottom, so output is stable across runs.
+pub fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by(|a, b| (&a.path, a.line, a.column).cmp(&(&b.path, b.line, b.column)));
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+}
+
+/// One-line summary such as `Found 12 warnings and 3 errors across 7 files`.
+pub fn summary(diagnostics: &[Diagnostic]) -> String {
+    if diagnostics.is_empty() {
+        return "No issues found".to_string();
+    }
+
+    let count = |level: DiagnosticLevel| diagnostics.iter().filter(|d| d.level == level).count();
+    let mut parts = Vec::new();
+    for (level, noun) in [
+        (DiagnosticLevel::Warning, "warning"),
+        (DiagnosticLevel::Error, "error"),
+        (DiagnosticLevel::Info, "info"),
+    ] {
+        let n = count(level);
+        if n > 0 {
+            parts.push(plural(n, noun));
+        }
+    }
+    let files: std::collections::HashSet<&str> = diagnostics.iter().map(|d| d.path.as_str()).collect();
+
+    let listed = match parts.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => unreachable!("diagnostics is non-empty"),
+    };
+    format!("Found {} across {}", listed, plural(files.len(), "file"))
+}
+
+/// Diagnostic counts per rule code, most frequent first, ties broken by code.
+pub fn statistics(diagnostics: &[Diagnostic]) -> Vec<(String, usize)> {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for diagnostic in diagnostics {
+        *counts.entry(diagnostic.code.as_str()).or_default() += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().map(|(code, n)| (code.to_string(), n)).collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+pub fn to_json(diagnostics: &[Diagnostic]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(diagnostics)?)
+}
+
+const TAB_WIDTH: usize = 8;
+
+/// Renders `line` with a caret under the 1-based character `column`. Tabs are
+/// expanded to spaces so the caret lines up however the line is indented.
+pub fn render_snippet(line: &str, column: usize) -> String {
+    let mut expanded = String::new();
+    let mut caret_offset = None;
+    for (i, c) in line.chars().enumerate() {
+        if i + 1 == column {
+            caret_offset = Some(expanded.chars().count());
+        }
+        if c == '\t' {
+            let width = TAB_WIDTH - expanded.chars().count() % TAB_WIDTH;
+            expanded.extend(std::iter::repeat_n(' ', width));
+        } else {
+            expanded.push(c);
+        }
+    }
+    // Columns past the end (e.g. a missing newline) point just after the text
+    let caret_offset = caret_offset.unwrap_or_else(|| expanded.chars().count());
+    format!("{}\n{}^", expanded, " ".repeat(caret_offset))
+}
+
+/// Renders one `::warning file=...,line=...,col=...::message` workflow command per diagnostic.
+pub fn to_github(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let command = match diagnostic.level {
+                DiagnosticLevel::Error => "error",
+                DiagnosticLevel::Warning => "warning",
+                DiagnosticLevel::Info => "notice",
+            };
+            format!(
+                "::{} file={},line={},col={}::{}",
+                command,
+                escape_github_property(&diagnostic.path),
+                diagnostic.line,
+                diagnostic.column,
+                escape_github_data(&diagnostic.message)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_github_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_github_property(value: &str) -> String {
+    escape_github_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Renders a JUnit XML report in which every linted file is a test case and
+/// each of its diagnostics a failure. `files` lists the linted paths, so files
+/// without diagnostics show up as passing test cases.
+pub fn to_junit(diagnostics: &[Diagnostic], files: &[String]) -> String {
+    let mut paths: Vec<&str> = files
+        .iter()
+        .map(String::as_str)
+        .chain(diagnostics.iter().map(|d| d.path.as_str()))
+        .collect();
+    paths.sort();
+    paths.dedup();
+
+    let failures = diagnostics.len();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!("<testsuites name=\"rustlint\" tests=\"{}\" failures=\"{}\">\n", paths.len(), failures));
+    xml.push_str(&format!(
+        "  <testsuite name=\"rustlint\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+        paths.len(),
+        failures
+    ));
+    for path in paths {
+        let path_attr = escape_xml(path);
+        xml.push_str(&format!("    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"", path_attr, path_attr, path_attr));
+        let file_diagnostics: Vec<_> = diagnostics.iter().filter(|d| d.path == path).collect();
+        if file_diagnostics.is_empty() {
+            xml.push_str("/>\n");
+            continue;
+        }
+        xml.push_str(">\n");
+        for diagnostic in file_diagnostics {
+            xml.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}:{}:{}: {} {}</failure>\n",
+                escape_xml(&diagnostic.message),
+                escape_xml(&diagnostic.code),
+                path_attr,
+                diagnostic.line,
+                diagnostic.column,
+                escape_xml(&diagnostic.code),
+                escape_xml(&diagnostic.message)
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+    xml.push_str("  </testsuite>\n</testsuites>");
+    xml
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Renders the diagnostics as a SARIF 2.1.0 log, with paths made relative to `base`.
+pub fn to_sarif(diagnostics: &[Diagnostic], rules: &[Box<dyn Rule + Sync>], base: &Path) -> Result<String> {
+    let sarif_rules: Vec<Value> = rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.code(),
+                "shortDescription": { "text": rule.description() },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            // Point diagnostics, or ones with an unset end, cover a single character
+            let (line, column) = (diagnostic.line.max(1), diagnostic.column.max(1));
+            let (end_line, end_column) = if diagnostic.end_line > line {
+                (diagnostic.end_line, diagnostic.end_column.max(1))
+            } else {
+                (line, diagnostic.end_column.max(column))
+            };
+            let mut result = json!({
+                "ruleId": diagnostic.code,
+                "level": sarif_level(&diagnostic.level),
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": relative_uri(&diagnostic.path, base) },
+                        "region": {
+                            "startLine": line,
+                            "startColumn": column,
+                            "endLine": end_line,
+                            // SARIF end columns are exclusive, ours are inclusive
+                            "endColumn": end_column + 1,
+                        },
+                    },
+                }],
+            });
+            if let Some(index) = rules.iter().position(|rule| rule.code() == diagnostic.code) {
+                result["ruleIndex"] = json!(index);
+            }
+            result
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "rustlint",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/sujalsin/rustlint",
+                    "rules": sarif_rules,
+                },
+            },
+            "results": results,
+        }],
+    });
+
+    Ok(serde_json::to_string_pretty(&log)?)
+}
+
+fn sarif_level(level: &DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+        DiagnosticLevel::Info => "note",
+    }
+}
+
+fn relative_uri(path: &str, base: &Path) -> String {
+    let path = Path::new(path);
+    let relative = path.strip_prefix(base).unwrap_or(path);
+    let relative = relative.strip_prefix(".").unwrap_or(relative);
+    relative.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,60 @@
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Width of the bar itself, between the brackets.
+const BAR_WIDTH: usize = 30;
+
+/// A one-line progress bar counting finished files. Worker threads call
+/// [`ProgressBar::inc`] concurrently; each call redraws the line in place.
+pub struct ProgressBar {
+    total: usize,
+    done: AtomicUsize,
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl ProgressBar {
+    /// A bar drawn on stderr, so it never mixes with the report on stdout.
+    pub fn new(total: usize) -> Self {
+        Self::with_writer(total, Box::new(io::stderr()))
+    }
+
+    pub fn with_writer(total: usize, out: Box<dyn Write + Send>) -> Self {
+        Self { total, done: AtomicUsize::new(0), out: Mutex::new(out) }
+    }
+
+    /// Counts one more finished file and redraws the bar.
+    pub fn inc(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        // Drawing is best effort; a broken terminal shouldn't fail the run
+        if let Ok(mut out) = self.out.lock() {
+            let _ = write!(out, "\r{}", render_progress(done, self.total));
+            let _ = out.flush();
+        }
+    }
+
+    /// The number of files counted so far.
+    pub fn position(&self) -> usize {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Clears the bar so later output starts on a clean line.
+    pub fn finish(&self) {
+        if let Ok(mut out) = self.out.lock() {
+            let width = render_progress(self.total, self.total).len();
+            let _ = write!(out, "\r{}\r", " ".repeat(width));
+            let _ = out.flush();
+        }
+    }
+}
+
+/// Renders `[=====>     ] 12/40 files` for `done` out of `total` files.
+pub fn render_progress(done: usize, total: usize) -> String {
+    let filled = (done.min(total) * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+    let bar = if filled < BAR_WIDTH {
+        format!("{}>{}", "=".repeat(filled), " ".repeat(BAR_WIDTH - filled - 1))
+    } else {
+        "=".repeat(BAR_WIDTH)
+    };
+    format!("[{}] {}/{} files", bar, done, total)
+}
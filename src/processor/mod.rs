@@ -1,13 +1,45 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use anyhow::Result;
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
 use rayon::prelude::*;
-use crate::linter::{Diagnostic, lint_file};
-use crate::rules::Rule;
+use crate::cache::Cache;
+use crate::config::Paths;
+use crate::linter::{Diagnostic, RuleTimings, lint_content_timed, read_source};
+use crate::output::ProgressBar;
+use crate::rules::RuleRegistry;
+
+pub fn process_files(files: Vec<PathBuf>, rules: &RuleRegistry) -> Result<Vec<Diagnostic>> {
+    process_files_with_cache(files, rules, None)
+}
+
+/// Lints `files` in parallel, reusing cached diagnostics for files whose
+/// content hasn't changed since the last run with the same settings.
+pub fn process_files_with_cache(files: Vec<PathBuf>, rules: &RuleRegistry, cache: Option<&Cache>) -> Result<Vec<Diagnostic>> {
+    process_files_with_options(files, rules, &ProcessOptions { cache, ..ProcessOptions::default() })
+}
+
+/// Optional extras for a run of [`process_files_with_options`].
+#[derive(Default)]
+pub struct ProcessOptions<'a> {
+    /// Reuse and store diagnostics of unchanged files.
+    pub cache: Option<&'a Cache>,
+    /// Add the time spent in each rule. Files served from the cache don't run any rules.
+    pub timings: Option<&'a RuleTimings>,
+    /// Advance once per finished file.
+    pub progress: Option<&'a ProgressBar>,
+}
 
-pub fn process_files(files: Vec<PathBuf>, rules: &[Box<dyn Rule + Sync>]) -> Result<Vec<Diagnostic>> {
+/// Like [`process_files_with_cache`], with the extras in `options`.
+pub fn process_files_with_options(files: Vec<PathBuf>, rules: &RuleRegistry, options: &ProcessOptions) -> Result<Vec<Diagnostic>> {
+    let ProcessOptions { cache, timings, progress } = *options;
     let diagnostics: Result<Vec<_>> = files.par_iter()
-        .map(|file| -> Result<Vec<Diagnostic>> {
-            lint_file(file, rules)
+        .map(|file| {
+            let result = lint_file(file, rules, cache, timings);
+            if let Some(progress) = progress {
+                progress.inc();
+            }
+            result
         })
         .collect();
 
@@ -20,15 +52,55 @@ pub fn process_files(files: Vec<PathBuf>, rules: &[Box<dyn Rule + Sync>]) -> Res
     Ok(all_diagnostics)
 }
 
+fn lint_file(file: &Path, rules: &RuleRegistry, cache: Option<&Cache>, timings: Option<&RuleTimings>) -> Result<Vec<Diagnostic>> {
+    log::debug!("Linting {}", file.display());
+    let content = read_source(file)?;
+    let Some(cache) = cache else {
+        return lint_content_timed(&content, file, rules, timings);
+    };
+    if let Some(diagnostics) = cache.get(&content, file) {
+        log::debug!("Using cached diagnostics for {}", file.display());
+        return Ok(diagnostics);
+    }
+    let diagnostics = lint_content_timed(&content, file, rules, timings)?;
+    // A cache that can't be written only costs speed, not correctness
+    if let Err(e) = cache.put(&content, file, &diagnostics) {
+        log::warn!("Failed to write cache entry for {}: {}", file.display(), e);
+    }
+    Ok(diagnostics)
+}
+
+/// Builds a pool running `jobs` threads; 0 means one per logical CPU.
+pub fn thread_pool(jobs: usize) -> Result<rayon::ThreadPool> {
+    Ok(rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?)
+}
+
 pub fn find_python_files(dir: &PathBuf) -> Result<Vec<PathBuf>> {
-    use walkdir::WalkDir;
+    find_python_files_with_config(dir, &Paths::default())
+}
+
+/// Walks `dir` for files with one of the configured extensions, skipping
+/// anything matched by `.gitignore`, `.ignore`, or the `exclude` globs.
+/// Symlinked directories are only followed with `follow_symlinks`; the walker
+/// reports a symlink loop as an error entry, which is dropped.
+pub fn find_python_files_with_config(dir: &PathBuf, paths: &Paths) -> Result<Vec<PathBuf>> {
+    let mut overrides = OverrideBuilder::new(dir);
+    for pattern in &paths.exclude {
+        overrides.add(&format!("!{}", pattern))?;
+    }
+
+    let walker = WalkBuilder::new(dir)
+        .hidden(false)
+        .require_git(false)
+        .follow_links(paths.follow_symlinks)
+        .overrides(overrides.build()?)
+        .build();
 
-    let python_files: Vec<PathBuf> = WalkDir::new(dir)
-        .into_iter()
+    let python_files: Vec<PathBuf> = walker
         .filter_map(|e| e.ok())
         .filter(|e| {
-            e.file_type().is_file() && 
-            e.path().extension().map_or(false, |ext| ext == "py")
+            e.file_type().is_some_and(|t| t.is_file()) &&
+            e.path().extension().is_some_and(|ext| paths.extensions.iter().any(|allowed| ext == allowed.as_str()))
         })
         .map(|e| e.path().to_path_buf())
         .collect();
@@ -42,7 +114,6 @@ mod tests {
     use std::fs::{self, File};
     use std::io::Write;
     use tempfile::tempdir;
-    use crate::rules::get_default_rules;
 
     #[test]
     fn test_find_python_files() -> Result<()> {
@@ -82,10 +153,7 @@ mod tests {
     #[test]
     fn test_parallel_processing() -> Result<()> {
         let dir = tempdir()?;
-        let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
-            .into_iter()
-            .map(|r| r as Box<dyn Rule + Sync>)
-            .collect();
+        let rules = RuleRegistry::with_defaults();
         
         // Create multiple test files with various issues
         let files = vec![
@@ -129,10 +197,7 @@ mod tests {
     #[test]
     fn test_parallel_processing_large_files() -> Result<()> {
         let dir = tempdir()?;
-        let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
-            .into_iter()
-            .map(|r| r as Box<dyn Rule + Sync>)
-            .collect();
+        let rules = RuleRegistry::with_defaults();
         
         // Create 10 large files with various issues
         let mut file_paths = Vec::new();
@@ -160,7 +225,7 @@ mod tests {
         let messages: Vec<_> = diagnostics.iter().map(|d| &d.message).collect();
         assert!(messages.iter().any(|m| m.contains("Unused import")), 
             "No unused import diagnostic found in: {:?}", messages);
-        assert!(diagnostics.len() > 0, "Expected at least one diagnostic");
+        assert!(!diagnostics.is_empty(), "Expected at least one diagnostic");
 
         Ok(())
     }
@@ -175,10 +240,7 @@ mod tests {
 
     #[test]
     fn test_process_empty_file_list() -> Result<()> {
-        let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
-            .into_iter()
-            .map(|r| r as Box<dyn Rule + Sync>)
-            .collect();
+        let rules = RuleRegistry::with_defaults();
         let diagnostics = process_files(Vec::new(), &rules)?;
         assert!(diagnostics.is_empty());
         Ok(())
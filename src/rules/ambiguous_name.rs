@@ -0,0 +1,82 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct AmbiguousName {
+    names: Vec<String>,
+}
+
+impl AmbiguousName {
+    pub fn new(names: Vec<String>) -> Self {
+        Self { names }
+    }
+}
+
+struct AmbiguousNameVisitor<'a> {
+    names: &'a [String],
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl AmbiguousNameVisitor<'_> {
+    fn check_name(&mut self, name: &str, location: ast::Location) {
+        if !self.names.iter().any(|ambiguous| ambiguous == name) {
+            return;
+        }
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: format!("Ambiguous variable name '{}'", name),
+            line: location.row(),
+            column: location.column() + 1,
+            end_line: location.row(),
+            end_column: location.column() + name.chars().count(),
+            path: String::new(),
+            code: "E741".to_string(),
+        });
+    }
+}
+
+impl Visitor for AmbiguousNameVisitor<'_> {
+    // Covers assignment, `for`, `with ... as` and comprehension targets alike
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Name { id, ctx: ast::ExprContext::Store } = &expr.node {
+            self.check_name(id, expr.location);
+        }
+        visitor::walk_expr(self, expr);
+    }
+
+    fn visit_arguments(&mut self, arguments: &ast::Arguments) {
+        let args = arguments
+            .posonlyargs
+            .iter()
+            .chain(&arguments.args)
+            .chain(arguments.vararg.as_deref())
+            .chain(&arguments.kwonlyargs)
+            .chain(arguments.kwarg.as_deref());
+        for arg in args {
+            self.check_name(&arg.node.arg, arg.location);
+        }
+        visitor::walk_arguments(self, arguments);
+    }
+}
+
+impl super::Rule for AmbiguousName {
+    fn code(&self) -> &'static str {
+        "E741"
+    }
+
+    fn name(&self) -> &'static str {
+        "ambiguous-variable-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags variables and parameters named 'l', 'O' or 'I', which are easily mistaken for '1' and '0'."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = AmbiguousNameVisitor { names: &self.names, diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
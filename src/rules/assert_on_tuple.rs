@@ -0,0 +1,53 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct AssertOnTuple;
+
+struct AssertOnTupleVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for AssertOnTupleVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::Assert { test, .. } = &stmt.node {
+            // `assert ()` is always false rather than always true, so only non-empty tuples count
+            if matches!(&test.node, ast::ExprKind::Tuple { elts, .. } if !elts.is_empty()) {
+                let end = test.end_location.unwrap_or(test.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "Assertion on a non-empty tuple is always true".to_string(),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column(),
+                    path: String::new(),
+                    code: "F631".to_string(),
+                });
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for AssertOnTuple {
+    fn code(&self) -> &'static str {
+        "F631"
+    }
+
+    fn name(&self) -> &'static str {
+        "assert-tuple"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags assert statements whose test is a non-empty tuple, which is always true."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = AssertOnTupleVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
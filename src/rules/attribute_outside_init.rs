@@ -0,0 +1,139 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct AttributeOutsideInit;
+
+/// Methods that set up a fresh instance, so assigning attributes there is expected.
+const INITIALIZERS: &[&str] = &["__init__", "__new__", "__post_init__", "setUp", "asyncSetUp"];
+
+struct ClassVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Looks for `receiver.attr = ...` in one method body, not counting nested
+/// functions and classes, which may bind the name to something else.
+struct SelfAssignments<'a> {
+    receiver: &'a str,
+    found: bool,
+}
+
+impl SelfAssignments<'_> {
+    fn check_target(&mut self, target: &ast::Expr) {
+        match &target.node {
+            ast::ExprKind::Attribute { value, .. } => {
+                if matches!(&value.node, ast::ExprKind::Name { id, .. } if id == self.receiver) {
+                    self.found = true;
+                }
+            }
+            ast::ExprKind::Tuple { elts, .. } | ast::ExprKind::List { elts, .. } => {
+                for elt in elts {
+                    self.check_target(elt);
+                }
+            }
+            ast::ExprKind::Starred { value, .. } => self.check_target(value),
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for SelfAssignments<'_> {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } | ast::StmtKind::ClassDef { .. } => {
+                return;
+            }
+            ast::StmtKind::Assign { targets, .. } => {
+                for target in targets {
+                    self.check_target(target);
+                }
+            }
+            ast::StmtKind::AugAssign { target, .. } | ast::StmtKind::AnnAssign { target, .. } => self.check_target(target),
+            ast::StmtKind::For { target, .. } | ast::StmtKind::AsyncFor { target, .. } => self.check_target(target),
+            ast::StmtKind::With { items, .. } | ast::StmtKind::AsyncWith { items, .. } => {
+                for target in items.iter().filter_map(|item| item.optional_vars.as_deref()) {
+                    self.check_target(target);
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+/// Whether any instance method in a class body assigns an attribute on its receiver.
+fn assigns_instance_attributes(body: &[ast::Stmt]) -> bool {
+    body.iter().any(|stmt| match &stmt.node {
+        ast::StmtKind::FunctionDef { args, body, decorator_list, .. }
+        | ast::StmtKind::AsyncFunctionDef { args, body, decorator_list, .. } => {
+            // Class and static methods don't receive an instance
+            if decorator_list
+                .iter()
+                .any(|decorator| matches!(super::decorator_name(decorator), Some("classmethod" | "staticmethod")))
+            {
+                return false;
+            }
+            let Some(receiver) = args.posonlyargs.iter().chain(&args.args).next() else {
+                return false;
+            };
+            let mut assignments = SelfAssignments { receiver: &receiver.node.arg, found: false };
+            visitor::walk_suite(&mut assignments, body);
+            assignments.found
+        }
+        _ => false,
+    })
+}
+
+fn defines_initializer(body: &[ast::Stmt]) -> bool {
+    body.iter().any(|stmt| match &stmt.node {
+        ast::StmtKind::FunctionDef { name, .. } | ast::StmtKind::AsyncFunctionDef { name, .. } => {
+            INITIALIZERS.contains(&name.as_str())
+        }
+        _ => false,
+    })
+}
+
+impl Visitor for ClassVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::ClassDef { name, body, decorator_list, .. } = &stmt.node {
+            // Dataclasses generate their __init__ from the field annotations
+            let dataclass = decorator_list.iter().any(|decorator| super::decorator_name(decorator) == Some("dataclass"));
+            if !dataclass && !defines_initializer(body) && assigns_instance_attributes(body) {
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Class '{}' assigns instance attributes but has no __init__", name),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: stmt.location.row(),
+                    end_column: stmt.location.column() + 1,
+                    path: String::new(),
+                    code: "W0201".to_string(),
+                });
+            }
+        }
+        // Nested classes are checked on their own
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for AttributeOutsideInit {
+    fn code(&self) -> &'static str {
+        "W0201"
+    }
+
+    fn name(&self) -> &'static str {
+        "attribute-defined-outside-init"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags classes whose methods set instance attributes although the class defines no __init__."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = ClassVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct BlankLinesBeforeDef;
+
+pub struct BlankLinesBeforeMethod;
+
+/// The line a definition starts on, counting from its first decorator.
+fn definition_start(stmt: &ast::Stmt) -> Option<usize> {
+    let decorator_list = match &stmt.node {
+        ast::StmtKind::FunctionDef { decorator_list, .. }
+        | ast::StmtKind::AsyncFunctionDef { decorator_list, .. }
+        | ast::StmtKind::ClassDef { decorator_list, .. } => decorator_list,
+        _ => return None,
+    };
+    let first_decorator = decorator_list.iter().map(|decorator| decorator.location.row()).min();
+    // The decorator expression starts after its `@`, but on the same line
+    Some(first_decorator.unwrap_or(stmt.location.row()).min(stmt.location.row()))
+}
+
+/// Counts the blank lines directly above `line` (1-based), looking through comments.
+fn blank_lines_before(lines: &[&str], line: usize) -> usize {
+    let mut blanks = 0;
+    for text in lines[..line - 1].iter().rev() {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            blanks += 1;
+        } else if !trimmed.starts_with('#') {
+            break;
+        }
+    }
+    blanks
+}
+
+/// Checks that every definition in `suite` after the first is preceded by
+/// exactly `expected` blank lines.
+fn check_suite(suite: &[ast::Stmt], lines: &[&str], expected: usize, code: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (i, stmt) in suite.iter().enumerate() {
+        let Some(start) = definition_start(stmt) else {
+            continue;
+        };
+        // The first statement of a module or class body has nothing to be separated from
+        if i == 0 || start > lines.len() {
+            continue;
+        }
+
+        let found = blank_lines_before(lines, start);
+        if found != expected {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: format!(
+                    "Expected {} blank line{}, found {}",
+                    expected,
+                    if expected == 1 { "" } else { "s" },
+                    found
+                ),
+                line: start,
+                column: 1,
+                end_line: start,
+                end_column: 1,
+                path: String::new(),
+                code: code.to_string(),
+            });
+        }
+    }
+}
+
+/// Checks the methods of every class in `suite`, including nested classes.
+fn check_classes(suite: &[ast::Stmt], lines: &[&str], diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in suite {
+        if let ast::StmtKind::ClassDef { body, .. } = &stmt.node {
+            check_suite(body, lines, 1, "E301", diagnostics);
+            check_classes(body, lines, diagnostics);
+        }
+    }
+}
+
+impl super::Rule for BlankLinesBeforeDef {
+    fn code(&self) -> &'static str {
+        "E302"
+    }
+
+    fn name(&self) -> &'static str {
+        "blank-lines-before-def"
+    }
+
+    fn description(&self) -> &'static str {
+        "Expects exactly two blank lines before top-level functions and classes."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        check_suite(context.ast, &context.lines, 2, "E302", &mut diagnostics);
+        Ok(diagnostics)
+    }
+}
+
+impl super::Rule for BlankLinesBeforeMethod {
+    fn code(&self) -> &'static str {
+        "E301"
+    }
+
+    fn name(&self) -> &'static str {
+        "blank-lines-before-method"
+    }
+
+    fn description(&self) -> &'static str {
+        "Expects exactly one blank line before methods and nested classes."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        check_classes(context.ast, &context.lines, &mut diagnostics);
+        diagnostics.sort_by_key(|d| d.line);
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,74 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct BoolComparison;
+
+struct BoolComparisonVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn as_bool(expr: &ast::Expr) -> Option<bool> {
+    match &expr.node {
+        ast::ExprKind::Constant { value: ast::Constant::Bool(value), .. } => Some(*value),
+        _ => None,
+    }
+}
+
+impl Visitor for BoolComparisonVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            let operands: Vec<&ast::Expr> = std::iter::once(left.as_ref()).chain(comparators).collect();
+            for (i, op) in ops.iter().enumerate() {
+                if !matches!(op, ast::Cmpop::Eq | ast::Cmpop::NotEq) {
+                    continue;
+                }
+                let (lhs, rhs) = (operands[i], operands[i + 1]);
+                // `True == False` compares two literals; there's no condition to rewrite
+                let (literal, value) = match (as_bool(lhs), as_bool(rhs)) {
+                    (Some(_), Some(_)) | (None, None) => continue,
+                    (Some(value), None) => (lhs, value),
+                    (None, Some(value)) => (rhs, value),
+                };
+                let message = if value {
+                    "Comparison to True should be 'if cond:' or 'if cond is True:'"
+                } else {
+                    "Comparison to False should be 'if not cond:' or 'if cond is False:'"
+                };
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: message.to_string(),
+                    line: literal.location.row(),
+                    column: literal.location.column() + 1,
+                    end_line: literal.location.row(),
+                    end_column: literal.location.column() + 1,
+                    path: String::new(),
+                    code: "E712".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for BoolComparison {
+    fn code(&self) -> &'static str {
+        "E712"
+    }
+
+    fn name(&self) -> &'static str {
+        "true-false-comparison"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags comparisons to True or False using '==' or '!=' instead of testing the condition directly."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = BoolComparisonVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+const BUILTINS: &[&str] = &[
+    "abs", "all", "any", "bin", "bool", "bytes", "callable", "chr", "compile", "dict", "dir",
+    "divmod", "enumerate", "eval", "exec", "file", "filter", "float", "format", "frozenset",
+    "getattr", "globals", "hasattr", "hash", "help", "hex", "id", "input", "int", "isinstance",
+    "issubclass", "iter", "len", "list", "locals", "map", "max", "min", "next", "object", "oct",
+    "open", "ord", "pow", "print", "range", "repr", "reversed", "round", "set", "setattr",
+    "slice", "sorted", "str", "sum", "super", "tuple", "type", "vars", "zip",
+];
+
+pub struct BuiltinShadowing;
+
+struct BuiltinShadowingVisitor {
+    // Names bound directly in a class body are attributes, not shadowed builtins
+    in_class_body: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl BuiltinShadowingVisitor {
+    fn check_name(&mut self, name: &str, location: &ast::Location) {
+        if BUILTINS.contains(&name) {
+            self.diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: format!("Redefinition of builtin '{}'", name),
+                line: location.row(),
+                column: location.column() + 1,
+                end_line: location.row(),
+                end_column: location.column() + 1,
+                path: String::new(),
+                code: "A001".to_string(),
+            });
+        }
+    }
+
+    fn check_target(&mut self, target: &ast::Expr) {
+        match &target.node {
+            ast::ExprKind::Name { id, .. } => self.check_name(id, &target.location),
+            ast::ExprKind::Tuple { elts, .. } | ast::ExprKind::List { elts, .. } => {
+                for elt in elts {
+                    self.check_target(elt);
+                }
+            }
+            ast::ExprKind::Starred { value, .. } => self.check_target(value),
+            // Attribute and subscript targets (`obj.list = 1`) don't rebind a name
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for BuiltinShadowingVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, .. } | ast::StmtKind::AsyncFunctionDef { name, .. } => {
+                if !self.in_class_body {
+                    self.check_name(name, &stmt.location);
+                }
+                let in_class_body = std::mem::replace(&mut self.in_class_body, false);
+                visitor::walk_stmt(self, stmt);
+                self.in_class_body = in_class_body;
+                return;
+            }
+            ast::StmtKind::ClassDef { .. } => {
+                let in_class_body = std::mem::replace(&mut self.in_class_body, true);
+                visitor::walk_stmt(self, stmt);
+                self.in_class_body = in_class_body;
+                return;
+            }
+            ast::StmtKind::Assign { targets, .. } if !self.in_class_body => {
+                for target in targets {
+                    self.check_target(target);
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_arguments(&mut self, arguments: &ast::Arguments) {
+        let all_args = arguments
+            .posonlyargs
+            .iter()
+            .chain(&arguments.args)
+            .chain(arguments.vararg.as_deref())
+            .chain(&arguments.kwonlyargs)
+            .chain(arguments.kwarg.as_deref());
+        for arg in all_args {
+            self.check_name(&arg.node.arg, &arg.location);
+        }
+        visitor::walk_arguments(self, arguments);
+    }
+}
+
+impl super::Rule for BuiltinShadowing {
+    fn code(&self) -> &'static str {
+        "A001"
+    }
+
+    fn name(&self) -> &'static str {
+        "builtin-shadowing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags variables, functions and parameters that shadow a Python builtin."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = BuiltinShadowingVisitor {
+            in_class_body: false,
+            diagnostics: Vec::new(),
+        };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct Complexity {
+    max_complexity: usize,
+}
+
+impl Complexity {
+    pub fn new(max_complexity: usize) -> Self {
+        Self { max_complexity }
+    }
+}
+
+/// Counts the branch points of a single function body, leaving nested
+/// functions to be scored on their own.
+struct BranchCounter {
+    branches: usize,
+}
+
+impl Visitor for BranchCounter {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } => return,
+            ast::StmtKind::If { .. }
+            | ast::StmtKind::For { .. }
+            | ast::StmtKind::AsyncFor { .. }
+            | ast::StmtKind::While { .. } => self.branches += 1,
+            ast::StmtKind::Try { handlers, .. } => self.branches += handlers.len(),
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match &expr.node {
+            ast::ExprKind::BoolOp { values, .. } => self.branches += values.len().saturating_sub(1),
+            ast::ExprKind::IfExp { .. } => self.branches += 1,
+            _ => {}
+        }
+        visitor::walk_expr(self, expr);
+    }
+
+    fn visit_comprehension(&mut self, comprehension: &ast::Comprehension) {
+        self.branches += comprehension.ifs.len();
+        visitor::walk_comprehension(self, comprehension);
+    }
+}
+
+struct ComplexityVisitor {
+    max_complexity: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for ComplexityVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::FunctionDef { name, body, .. }
+        | ast::StmtKind::AsyncFunctionDef { name, body, .. } = &stmt.node
+        {
+            let mut counter = BranchCounter { branches: 0 };
+            visitor::walk_suite(&mut counter, body);
+            let complexity = counter.branches + 1;
+            if complexity > self.max_complexity {
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!(
+                        "Function '{}' is too complex ({} > {})",
+                        name, complexity, self.max_complexity
+                    ),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: stmt.location.row(),
+                    end_column: stmt.location.column() + 1,
+                    path: String::new(),
+                    code: "C901".to_string(),
+                });
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for Complexity {
+    fn code(&self) -> &'static str {
+        "C901"
+    }
+
+    fn name(&self) -> &'static str {
+        "too-complex"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags functions whose cyclomatic complexity exceeds the configured maximum."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = ComplexityVisitor {
+            max_complexity: self.max_complexity,
+            diagnostics: Vec::new(),
+        };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use std::cmp::Ordering;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct ConstantComparison;
+
+struct ConstantComparisonVisitor<'a> {
+    source: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A literal whose comparisons can be folded. Booleans compare as integers,
+/// as they do in Python.
+enum Literal<'a> {
+    Int(i64),
+    Float(f64),
+    Str(&'a str),
+}
+
+fn literal(expr: &ast::Expr) -> Option<Literal<'_>> {
+    match &expr.node {
+        ast::ExprKind::Constant { value, .. } => match value {
+            // Integers too large for i64 aren't worth folding
+            ast::Constant::Int(value) => i64::try_from(value).ok().map(Literal::Int),
+            ast::Constant::Float(value) => Some(Literal::Float(*value)),
+            ast::Constant::Bool(value) => Some(Literal::Int(i64::from(*value))),
+            ast::Constant::Str(value) => Some(Literal::Str(value)),
+            _ => None,
+        },
+        ast::ExprKind::UnaryOp { op: ast::Unaryop::USub, operand } => match literal(operand)? {
+            Literal::Int(value) => value.checked_neg().map(Literal::Int),
+            Literal::Float(value) => Some(Literal::Float(-value)),
+            Literal::Str(_) => None,
+        },
+        _ => None,
+    }
+}
+
+/// Orders an integer against a float exactly, as Python does, rather than
+/// rounding the integer to the nearest float.
+fn compare_int_float(int: i64, float: f64) -> Option<Ordering> {
+    // 2^63, the first float past the i64 range
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if float.is_nan() {
+        None
+    } else if float >= LIMIT {
+        Some(Ordering::Less)
+    } else if float < -LIMIT {
+        Some(Ordering::Greater)
+    } else {
+        // The whole part fits in an i64, so compare it exactly, then the fraction
+        Some(int.cmp(&(float.trunc() as i64)).then(0.0.partial_cmp(&float.fract())?))
+    }
+}
+
+/// Evaluates `lhs op rhs`, or `None` if the operands' types differ or the operator isn't foldable.
+fn compare(lhs: &Literal, op: &ast::Cmpop, rhs: &Literal) -> Option<bool> {
+    let ordering = match (lhs, rhs) {
+        (Literal::Int(a), Literal::Int(b)) => Some(a.cmp(b)),
+        (Literal::Float(a), Literal::Float(b)) => a.partial_cmp(b),
+        (Literal::Int(a), Literal::Float(b)) => compare_int_float(*a, *b),
+        (Literal::Float(a), Literal::Int(b)) => compare_int_float(*b, *a).map(Ordering::reverse),
+        (Literal::Str(a), Literal::Str(b)) => Some(a.cmp(b)),
+        // Mixed types are reported by the W0136 rule, and ordering them raises
+        _ => return None,
+    };
+    Some(match op {
+        ast::Cmpop::Eq => ordering == Some(Ordering::Equal),
+        ast::Cmpop::NotEq => ordering != Some(Ordering::Equal),
+        ast::Cmpop::Lt => ordering == Some(Ordering::Less),
+        ast::Cmpop::LtE => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        ast::Cmpop::Gt => ordering == Some(Ordering::Greater),
+        ast::Cmpop::GtE => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        _ => return None,
+    })
+}
+
+impl Visitor for ConstantComparisonVisitor<'_> {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            let operands: Option<Vec<Literal>> = std::iter::once(left.as_ref()).chain(comparators).map(literal).collect();
+            // A chain `a < b < c` is true only if every link is
+            let result = operands.and_then(|operands| {
+                ops.iter()
+                    .enumerate()
+                    .map(|(i, op)| compare(&operands[i], op, &operands[i + 1]))
+                    .collect::<Option<Vec<bool>>>()
+            });
+            if let Some(results) = result {
+                let value = if results.iter().all(|&r| r) { "True" } else { "False" };
+                let end = expr.end_location.unwrap_or(expr.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!(
+                        "Comparison '{}' is always {}",
+                        super::expr_source(self.source, expr).unwrap_or("..."),
+                        value
+                    ),
+                    line: expr.location.row(),
+                    column: expr.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(expr.location.column() + 1),
+                    path: String::new(),
+                    code: "PLR0133".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for ConstantComparison {
+    fn code(&self) -> &'static str {
+        "PLR0133"
+    }
+
+    fn name(&self) -> &'static str {
+        "comparison-of-constants"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags comparisons between two literals, whose result is always the same."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = ConstantComparisonVisitor { source: context.source, diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
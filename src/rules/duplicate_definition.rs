@@ -0,0 +1,97 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::{is_overload, nested_blocks};
+use std::collections::HashMap;
+
+pub struct DuplicateDefinition;
+
+/// Whether `decorator` is `@name.setter` (or `.getter`/`.deleter`), which
+/// extends the property `name` instead of replacing it.
+fn is_property_accessor(decorator: &ast::Expr, name: &str) -> bool {
+    match &decorator.node {
+        ast::ExprKind::Attribute { value, attr, .. } => {
+            matches!(attr.as_str(), "setter" | "getter" | "deleter")
+                && matches!(&value.node, ast::ExprKind::Name { id, .. } if id == name)
+        }
+        _ => false,
+    }
+}
+
+/// A function or class defined directly in a block.
+struct Definition<'a> {
+    kind: &'static str,
+    name: &'a str,
+    decorators: &'a [ast::Expr],
+}
+
+fn definition(stmt: &ast::Stmt) -> Option<Definition<'_>> {
+    match &stmt.node {
+        ast::StmtKind::FunctionDef { name, decorator_list, .. }
+        | ast::StmtKind::AsyncFunctionDef { name, decorator_list, .. } => {
+            Some(Definition { kind: "function", name, decorators: decorator_list })
+        }
+        ast::StmtKind::ClassDef { name, decorator_list, .. } => {
+            Some(Definition { kind: "class", name, decorators: decorator_list })
+        }
+        _ => None,
+    }
+}
+
+/// Checks the definitions made directly in `block`. Definitions in different
+/// branches of an `if` or `try` are alternatives, so each nested block is
+/// checked on its own.
+fn check_block(block: &[ast::Stmt], diagnostics: &mut Vec<Diagnostic>) {
+    // Line of the latest definition of each name, and whether it was an @overload stub
+    let mut defined: HashMap<&str, (usize, bool)> = HashMap::new();
+
+    for stmt in block {
+        if let Some(def) = definition(stmt) {
+            let overload = def.decorators.iter().any(is_overload);
+            let accessor = def.decorators.iter().any(|decorator| is_property_accessor(decorator, def.name));
+            if let Some(&(previous, previous_overload)) = defined.get(def.name) {
+                if !overload && !previous_overload && !accessor {
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: format!(
+                            "Redefinition of {} '{}' (previous definition on line {})",
+                            def.kind, def.name, previous
+                        ),
+                        line: stmt.location.row(),
+                        column: stmt.location.column() + 1,
+                        end_line: stmt.location.row(),
+                        end_column: stmt.location.column() + 1,
+                        path: String::new(),
+                        code: "E0102".to_string(),
+                    });
+                }
+            }
+            defined.insert(def.name, (stmt.location.row(), overload));
+        }
+
+        for nested in nested_blocks(stmt) {
+            check_block(nested, diagnostics);
+        }
+    }
+}
+
+impl super::Rule for DuplicateDefinition {
+    fn code(&self) -> &'static str {
+        "E0102"
+    }
+
+    fn name(&self) -> &'static str {
+        "function-redefined"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags functions and classes defined twice in the same block, which silently replaces the first definition."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        check_block(context.ast, &mut diagnostics);
+        Ok(diagnostics)
+    }
+}
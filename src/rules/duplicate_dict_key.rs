@@ -0,0 +1,92 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct DuplicateDictKey;
+
+struct DuplicateDictKeyVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// A constant key reduced to how Python hashes it, so `1`, `1.0` and `True`
+/// all compare equal.
+#[derive(PartialEq)]
+enum KeyValue {
+    Str(String),
+    Bytes(Vec<u8>),
+    Number(String),
+    None,
+}
+
+fn key_value(expr: &ast::Expr) -> Option<(KeyValue, String)> {
+    let ast::ExprKind::Constant { value, .. } = &expr.node else {
+        return None;
+    };
+    let key = match value {
+        ast::Constant::Str(s) => (KeyValue::Str(s.clone()), format!("{:?}", s)),
+        ast::Constant::Bytes(b) => (KeyValue::Bytes(b.clone()), format!("b{:?}", String::from_utf8_lossy(b))),
+        ast::Constant::Int(i) => (KeyValue::Number(i.to_string()), i.to_string()),
+        ast::Constant::Bool(b) => (KeyValue::Number((*b as u8).to_string()), if *b { "True" } else { "False" }.to_string()),
+        ast::Constant::Float(f) => {
+            let normalized = if f.is_finite() && f.fract() == 0.0 && f.abs() < 1e18 {
+                (*f as i64).to_string()
+            } else {
+                f.to_string()
+            };
+            (KeyValue::Number(normalized), format!("{:?}", f))
+        }
+        ast::Constant::None => (KeyValue::None, "None".to_string()),
+        _ => return None,
+    };
+    Some(key)
+}
+
+impl Visitor for DuplicateDictKeyVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Dict { keys, .. } = &expr.node {
+            let mut seen: Vec<KeyValue> = Vec::new();
+            for key in keys {
+                let Some((value, display)) = key_value(key) else {
+                    continue;
+                };
+                if seen.contains(&value) {
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: format!("Duplicate key {} in dict literal", display),
+                        line: key.location.row(),
+                        column: key.location.column() + 1,
+                        end_line: key.location.row(),
+                        end_column: key.location.column() + 1,
+                        path: String::new(),
+                        code: "F601".to_string(),
+                    });
+                } else {
+                    seen.push(value);
+                }
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for DuplicateDictKey {
+    fn code(&self) -> &'static str {
+        "F601"
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicate-dict-key"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags dict literals that repeat a constant key, where the later value silently wins."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = DuplicateDictKeyVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+/// Decorators marking a function whose body is intentionally a stub.
+const STUB_DECORATORS: &[&str] = &["abstractmethod", "abstractproperty", "overload"];
+
+/// Base classes whose bodies and methods are interface definitions.
+const INTERFACE_BASES: &[&str] = &["ABC", "Protocol"];
+
+pub struct EmptyBody;
+
+#[derive(Default)]
+struct EmptyBodyVisitor {
+    // Methods of a Protocol or ABC subclass are allowed to be stubs
+    in_interface: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether a class derives from an exception or warning class, judged by a
+/// base name ending in `Exception`, `Error` or `Warning`. Such classes are
+/// routinely declared with an empty body just to be raised and caught.
+fn is_exception(bases: &[ast::Expr]) -> bool {
+    bases.iter().any(|base| {
+        super::decorator_name(base)
+            .is_some_and(|name| ["Exception", "Error", "Warning"].iter().any(|suffix| name.ends_with(suffix)))
+    })
+}
+
+/// Whether a class derives from `ABC` or `Protocol` (possibly subscripted or
+/// module-qualified), or uses `ABCMeta` as its metaclass.
+fn is_interface(bases: &[ast::Expr], keywords: &[ast::Keyword]) -> bool {
+    bases.iter().any(|base| {
+        let base = match &base.node {
+            ast::ExprKind::Subscript { value, .. } => value,
+            _ => base,
+        };
+        super::decorator_name(base).is_some_and(|name| INTERFACE_BASES.contains(&name))
+    }) || keywords.iter().any(|keyword| {
+            keyword.node.arg.as_deref() == Some("metaclass")
+                && super::decorator_name(&keyword.node.value) == Some("ABCMeta")
+        })
+}
+
+impl EmptyBodyVisitor {
+    fn report(&mut self, stmt: &ast::Stmt, body: &[ast::Stmt]) {
+        let end = body.last().and_then(|last| last.end_location).unwrap_or(stmt.location);
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: "Empty body; add an implementation or a docstring".to_string(),
+            line: stmt.location.row(),
+            column: stmt.location.column() + 1,
+            end_line: end.row(),
+            end_column: end.column().max(1),
+            path: String::new(),
+            code: "W0010".to_string(),
+        });
+    }
+}
+
+impl Visitor for EmptyBodyVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { body, decorator_list, .. }
+            | ast::StmtKind::AsyncFunctionDef { body, decorator_list, .. } => {
+                let stub = decorator_list
+                    .iter()
+                    .any(|d| super::decorator_name(d).is_some_and(|name| STUB_DECORATORS.contains(&name)));
+                if super::is_empty_body(body) && !stub && !self.in_interface {
+                    self.report(stmt, body);
+                }
+                let in_interface = std::mem::replace(&mut self.in_interface, false);
+                visitor::walk_stmt(self, stmt);
+                self.in_interface = in_interface;
+            }
+            ast::StmtKind::ClassDef { body, bases, keywords, .. } => {
+                let interface = is_interface(bases, keywords);
+                if super::is_empty_body(body) && !interface && !is_exception(bases) {
+                    self.report(stmt, body);
+                }
+                let in_interface = std::mem::replace(&mut self.in_interface, interface);
+                visitor::walk_stmt(self, stmt);
+                self.in_interface = in_interface;
+            }
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+}
+
+impl super::Rule for EmptyBody {
+    fn code(&self) -> &'static str {
+        "W0010"
+    }
+
+    fn name(&self) -> &'static str {
+        "empty-body"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags functions and classes whose body is only 'pass' or '...' with no docstring, which are probably unfinished."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = EmptyBodyVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+/// Builtin exceptions and their direct base class.
+const EXCEPTION_PARENTS: &[(&str, &str)] = &[
+    ("SystemExit", "BaseException"),
+    ("KeyboardInterrupt", "BaseException"),
+    ("GeneratorExit", "BaseException"),
+    ("Exception", "BaseException"),
+    ("ArithmeticError", "Exception"),
+    ("FloatingPointError", "ArithmeticError"),
+    ("OverflowError", "ArithmeticError"),
+    ("ZeroDivisionError", "ArithmeticError"),
+    ("AssertionError", "Exception"),
+    ("AttributeError", "Exception"),
+    ("BufferError", "Exception"),
+    ("EOFError", "Exception"),
+    ("ImportError", "Exception"),
+    ("ModuleNotFoundError", "ImportError"),
+    ("LookupError", "Exception"),
+    ("IndexError", "LookupError"),
+    ("KeyError", "LookupError"),
+    ("MemoryError", "Exception"),
+    ("NameError", "Exception"),
+    ("UnboundLocalError", "NameError"),
+    ("OSError", "Exception"),
+    ("BlockingIOError", "OSError"),
+    ("ChildProcessError", "OSError"),
+    ("ConnectionError", "OSError"),
+    ("BrokenPipeError", "ConnectionError"),
+    ("ConnectionAbortedError", "ConnectionError"),
+    ("ConnectionRefusedError", "ConnectionError"),
+    ("ConnectionResetError", "ConnectionError"),
+    ("FileExistsError", "OSError"),
+    ("FileNotFoundError", "OSError"),
+    ("InterruptedError", "OSError"),
+    ("IsADirectoryError", "OSError"),
+    ("NotADirectoryError", "OSError"),
+    ("PermissionError", "OSError"),
+    ("ProcessLookupError", "OSError"),
+    ("TimeoutError", "OSError"),
+    ("ReferenceError", "Exception"),
+    ("RuntimeError", "Exception"),
+    ("NotImplementedError", "RuntimeError"),
+    ("RecursionError", "RuntimeError"),
+    ("StopIteration", "Exception"),
+    ("StopAsyncIteration", "Exception"),
+    ("SyntaxError", "Exception"),
+    ("IndentationError", "SyntaxError"),
+    ("TabError", "IndentationError"),
+    ("SystemError", "Exception"),
+    ("TypeError", "Exception"),
+    ("ValueError", "Exception"),
+    ("UnicodeError", "ValueError"),
+    ("UnicodeDecodeError", "UnicodeError"),
+    ("UnicodeEncodeError", "UnicodeError"),
+    ("UnicodeTranslateError", "UnicodeError"),
+    ("Warning", "Exception"),
+    ("BytesWarning", "Warning"),
+    ("DeprecationWarning", "Warning"),
+    ("EncodingWarning", "Warning"),
+    ("FutureWarning", "Warning"),
+    ("ImportWarning", "Warning"),
+    ("PendingDeprecationWarning", "Warning"),
+    ("ResourceWarning", "Warning"),
+    ("RuntimeWarning", "Warning"),
+    ("SyntaxWarning", "Warning"),
+    ("UnicodeWarning", "Warning"),
+    ("UserWarning", "Warning"),
+];
+
+pub struct ExceptOrder;
+
+#[derive(Default)]
+struct ExceptOrderVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Resolves the legacy aliases of `OSError`.
+fn canonical(name: &str) -> &str {
+    match name {
+        "IOError" | "EnvironmentError" => "OSError",
+        _ => name,
+    }
+}
+
+/// Whether catching `ancestor` also catches `name`.
+fn catches(ancestor: &str, name: &str) -> bool {
+    let ancestor = canonical(ancestor);
+    let mut current = canonical(name);
+    loop {
+        if current == ancestor {
+            return true;
+        }
+        match EXCEPTION_PARENTS.iter().find(|(child, _)| *child == current) {
+            Some((_, parent)) => current = parent,
+            None => return false,
+        }
+    }
+}
+
+/// The builtin exception names a handler catches, with their expressions.
+/// User-defined and qualified exceptions are skipped since their bases are unknown.
+fn caught(handler: &ast::Excepthandler) -> Vec<(&str, &ast::Expr)> {
+    let ast::ExcepthandlerKind::ExceptHandler { type_: Some(type_), .. } = &handler.node else {
+        return Vec::new();
+    };
+    let types = match &type_.node {
+        ast::ExprKind::Tuple { elts, .. } => elts.iter().collect(),
+        _ => vec![type_.as_ref()],
+    };
+    types
+        .into_iter()
+        .filter_map(|expr| match &expr.node {
+            ast::ExprKind::Name { id, .. } if catches("BaseException", id) => Some((id.as_str(), expr)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl Visitor for ExceptOrderVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::Try { handlers, .. } = &stmt.node {
+            let mut earlier: Vec<&str> = Vec::new();
+            for handler in handlers {
+                let names = caught(handler);
+                for (name, expr) in &names {
+                    if let Some(broader) = earlier.iter().find(|broader| catches(broader, name)) {
+                        let end = expr.end_location.unwrap_or(expr.location);
+                        self.diagnostics.push(Diagnostic {
+                            level: DiagnosticLevel::Warning,
+                            message: format!("Except handler '{}' is unreachable; '{}' catches it first", name, broader),
+                            line: expr.location.row(),
+                            column: expr.location.column() + 1,
+                            end_line: end.row(),
+                            end_column: end.column().max(expr.location.column() + 1),
+                            path: String::new(),
+                            code: "E0701".to_string(),
+                        });
+                    }
+                }
+                earlier.extend(names.iter().map(|(name, _)| *name));
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for ExceptOrder {
+    fn code(&self) -> &'static str {
+        "E0701"
+    }
+
+    fn name(&self) -> &'static str {
+        "bad-except-order"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags except handlers that can never run because an earlier handler catches a base class of their exception."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = ExceptOrderVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
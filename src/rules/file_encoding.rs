@@ -0,0 +1,72 @@
+use anyhow::Result;
+use regex::Regex;
+use std::sync::OnceLock;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct FileEncoding;
+
+/// PEP 263 encoding declaration, e.g. `# -*- coding: latin-1 -*-`.
+fn coding_declaration() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^[ \t\f]*#.*?coding[:=][ \t]*([-\w.]+)").unwrap())
+}
+
+fn is_utf8(encoding: &str) -> bool {
+    let normalized = encoding.to_ascii_lowercase().replace('_', "-");
+    normalized == "utf-8" || normalized == "utf8" || normalized.starts_with("utf-8-")
+}
+
+impl super::Rule for FileEncoding {
+    fn code(&self) -> &'static str {
+        "E902"
+    }
+
+    fn name(&self) -> &'static str {
+        "file-encoding"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags a UTF-8 byte order mark and encoding declarations other than utf-8."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+
+        if context.source.starts_with('\u{feff}') {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "File starts with a UTF-8 BOM".to_string(),
+                line: 1,
+                column: 1,
+                end_line: 1,
+                end_column: 1,
+                path: String::new(),
+                code: "E902".to_string(),
+            });
+        }
+
+        // PEP 263 only honors a declaration on the first or second line
+        for (i, line) in context.source.trim_start_matches('\u{feff}').lines().take(2).enumerate() {
+            if let Some(captures) = coding_declaration().captures(line) {
+                let encoding = &captures[1];
+                if !is_utf8(encoding) {
+                    let column = captures.get(1).map_or(0, |m| line[..m.start()].chars().count()) + 1;
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: "Non-utf-8 encoding declaration".to_string(),
+                        line: i + 1,
+                        column,
+                        end_line: i + 1,
+                        end_column: column + encoding.chars().count() - 1,
+                        path: String::new(),
+                        code: "E902".to_string(),
+                    });
+                }
+                break;
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct FinalNewline;
+
+impl super::Rule for FinalNewline {
+    fn code(&self) -> &'static str {
+        "W292"
+    }
+
+    fn name(&self) -> &'static str {
+        "missing-newline-at-end-of-file"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags a file that does not end with a newline."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+
+        // Empty and whitespace-only files have no last line worth pointing at
+        if context.source.trim().is_empty() {
+            return Ok(diagnostics);
+        }
+
+        if !context.source.ends_with('\n') {
+            let last = context.lines.last().copied().unwrap_or("");
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "No newline at end of file".to_string(),
+                line: context.lines.len(),
+                column: last.chars().count() + 1,
+                end_line: context.lines.len(),
+                end_column: last.chars().count() + 1,
+                path: String::new(),
+                code: "W292".to_string(),
+            });
+        }
+
+        Ok(diagnostics)
+    }
+}
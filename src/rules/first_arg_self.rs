@@ -0,0 +1,105 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+/// Methods that are implicitly class methods and so take `cls`.
+const IMPLICIT_CLASSMETHODS: &[&str] = &["__new__", "__init_subclass__", "__class_getitem__"];
+
+/// Base classes that make a class a metaclass, whose methods take `cls`.
+const METACLASS_BASES: &[&str] = &["type", "ABCMeta"];
+
+pub struct FirstArgSelf;
+
+#[derive(Default)]
+struct FirstArgSelfVisitor {
+    // Only functions defined directly in a class body are methods
+    in_class_body: bool,
+    // Methods of a metaclass receive a class rather than an instance
+    in_metaclass: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl FirstArgSelfVisitor {
+    fn check_method(&mut self, stmt: &ast::Stmt, name: &str, args: &ast::Arguments, decorators: &[ast::Expr]) {
+        let has_decorator = |wanted: &str| decorators.iter().any(|d| super::decorator_name(d) == Some(wanted));
+        if has_decorator("staticmethod") {
+            return;
+        }
+        let expected = if self.in_metaclass || has_decorator("classmethod") || IMPLICIT_CLASSMETHODS.contains(&name) {
+            "cls"
+        } else {
+            "self"
+        };
+
+        let first = args.posonlyargs.iter().chain(&args.args).next();
+        // `def method(*args)` still receives the instance, just not by name
+        if first.is_none() && args.vararg.is_some() {
+            return;
+        }
+        if first.is_some_and(|arg| arg.node.arg == expected) {
+            return;
+        }
+
+        let location = first.map_or(stmt.location, |arg| arg.location);
+        let end = first.and_then(|arg| arg.end_location).unwrap_or(location);
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: format!("First argument of method '{}' should be '{}'", name, expected),
+            line: location.row(),
+            column: location.column() + 1,
+            end_line: end.row(),
+            end_column: end.column().max(location.column() + 1),
+            path: String::new(),
+            code: "N805".to_string(),
+        });
+    }
+}
+
+impl Visitor for FirstArgSelfVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, args, decorator_list, .. }
+            | ast::StmtKind::AsyncFunctionDef { name, args, decorator_list, .. } => {
+                if self.in_class_body {
+                    self.check_method(stmt, name, args, decorator_list);
+                }
+                let in_class_body = std::mem::replace(&mut self.in_class_body, false);
+                visitor::walk_stmt(self, stmt);
+                self.in_class_body = in_class_body;
+            }
+            ast::StmtKind::ClassDef { bases, .. } => {
+                let metaclass = bases
+                    .iter()
+                    .any(|base| super::decorator_name(base).is_some_and(|name| METACLASS_BASES.contains(&name)));
+                let in_class_body = std::mem::replace(&mut self.in_class_body, true);
+                let in_metaclass = std::mem::replace(&mut self.in_metaclass, metaclass);
+                visitor::walk_stmt(self, stmt);
+                self.in_class_body = in_class_body;
+                self.in_metaclass = in_metaclass;
+            }
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+}
+
+impl super::Rule for FirstArgSelf {
+    fn code(&self) -> &'static str {
+        "N805"
+    }
+
+    fn name(&self) -> &'static str {
+        "invalid-first-argument-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags methods whose first parameter isn't 'self', or 'cls' for class methods and metaclass methods."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = FirstArgSelfVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
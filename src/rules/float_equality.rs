@@ -0,0 +1,77 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct FloatEquality;
+
+struct FloatEqualityVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_float_literal(expr: &ast::Expr) -> bool {
+    match &expr.node {
+        ast::ExprKind::Constant { value: ast::Constant::Float(_), .. } => true,
+        ast::ExprKind::UnaryOp { op: ast::Unaryop::USub | ast::Unaryop::UAdd, operand } => is_float_literal(operand),
+        _ => false,
+    }
+}
+
+fn is_constant(expr: &ast::Expr) -> bool {
+    match &expr.node {
+        ast::ExprKind::Constant { .. } => true,
+        ast::ExprKind::UnaryOp { operand, .. } => is_constant(operand),
+        _ => false,
+    }
+}
+
+impl Visitor for FloatEqualityVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            let operands: Vec<&ast::Expr> = std::iter::once(left.as_ref()).chain(comparators).collect();
+            for (i, op) in ops.iter().enumerate() {
+                if !matches!(op, ast::Cmpop::Eq | ast::Cmpop::NotEq) {
+                    continue;
+                }
+                let (lhs, rhs) = (operands[i], operands[i + 1]);
+                // Two literals are the PLR0133 rule's business
+                if !(is_float_literal(lhs) || is_float_literal(rhs)) || (is_constant(lhs) && is_constant(rhs)) {
+                    continue;
+                }
+                let end = rhs.end_location.filter(|end| end.row() == lhs.location.row()).unwrap_or(lhs.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "Direct float equality comparison; consider math.isclose()".to_string(),
+                    line: lhs.location.row(),
+                    column: lhs.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(lhs.location.column() + 1),
+                    path: String::new(),
+                    code: "W0137".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for FloatEquality {
+    fn code(&self) -> &'static str {
+        "W0137"
+    }
+
+    fn name(&self) -> &'static str {
+        "float-equality"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags '==' and '!=' against float literals, which rounding makes unreliable; 'math.isclose()' is safer."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = FloatEqualityVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
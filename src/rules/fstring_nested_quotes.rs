@@ -0,0 +1,168 @@
+use anyhow::Result;
+use crate::config::PythonVersion;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct FStringNestedQuotes {
+    target_version: PythonVersion,
+}
+
+impl FStringNestedQuotes {
+    pub fn new(target_version: PythonVersion) -> Self {
+        Self { target_version }
+    }
+}
+
+/// PEP 701 lets f-string expressions reuse the enclosing quote from 3.12 on.
+const NESTED_QUOTES_VERSION: PythonVersion = PythonVersion::new(3, 12);
+
+/// Scans source text for f-strings; older grammars (and our parser) reject
+/// the nested quotes, so the check can't rely on the syntax tree.
+struct Scanner<'a> {
+    chars: &'a [char],
+    /// Offsets of the quotes that reopen an enclosing f-string's quote.
+    nested: Vec<usize>,
+}
+
+impl Scanner<'_> {
+    fn run(&mut self) {
+        let mut i = 0;
+        while i < self.chars.len() {
+            match self.chars[i] {
+                '#' => {
+                    while i < self.chars.len() && self.chars[i] != '\n' {
+                        i += 1;
+                    }
+                }
+                '"' | '\'' => i = self.string(i),
+                _ => i += 1,
+            }
+        }
+    }
+
+    /// The lowercased string prefix (`f`, `rb`, ...) directly before the quote at `i`.
+    fn prefix(&self, i: usize) -> String {
+        let start = (0..i)
+            .rev()
+            .take_while(|&j| self.chars[j].is_alphanumeric() || self.chars[j] == '_')
+            .last()
+            .unwrap_or(i);
+        let prefix: String = self.chars[start..i].iter().collect::<String>().to_lowercase();
+        match prefix.as_str() {
+            "f" | "rf" | "fr" | "r" | "b" | "rb" | "br" | "u" => prefix,
+            _ => String::new(),
+        }
+    }
+
+    /// Whether the string opened by `quote` (one or three characters) closes at `i`.
+    fn closes_at(&self, i: usize, quote: char, triple: bool) -> bool {
+        let len = if triple { 3 } else { 1 };
+        self.chars.get(i..i + len).is_some_and(|window| window.iter().all(|&c| c == quote))
+    }
+
+    /// Skips the string literal whose opening quote is at `start`, returning
+    /// the offset just past it. Unterminated strings end at the line break.
+    fn string(&mut self, start: usize) -> usize {
+        let quote = self.chars[start];
+        let triple = self.closes_at(start, quote, true);
+        let formatted = self.prefix(start).contains('f');
+        let quote_len = if triple { 3 } else { 1 };
+
+        let mut i = start + quote_len;
+        let mut depth = 0;
+        let mut brackets = 0;
+        let mut in_spec = false;
+        let mut reported = false;
+        while i < self.chars.len() {
+            let c = self.chars[i];
+            if c == '\n' && !triple {
+                return i;
+            }
+            if depth == 0 || in_spec {
+                match c {
+                    '\\' => i += 1,
+                    _ if depth == 0 && self.closes_at(i, quote, triple) => return i + quote_len,
+                    '{' if formatted && self.chars.get(i + 1) == Some(&'{') && depth == 0 => i += 1,
+                    '{' if formatted => {
+                        depth += 1;
+                        in_spec = false;
+                    }
+                    '}' if in_spec => {
+                        depth -= 1;
+                        in_spec = false;
+                    }
+                    _ => {}
+                }
+                i += 1;
+                continue;
+            }
+
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    // Only a nested field inside a format spec closes back into it
+                    in_spec = depth > 0;
+                }
+                '(' | '[' => brackets += 1,
+                ')' | ']' => brackets -= 1,
+                ':' if depth == 1 && brackets == 0 => in_spec = true,
+                '#' => {}
+                '"' | '\'' => {
+                    if c == quote && (!triple || self.closes_at(i, quote, true)) && !reported {
+                        self.nested.push(i);
+                        reported = true;
+                    }
+                    i = self.string(i);
+                    continue;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        i
+    }
+}
+
+impl super::Rule for FStringNestedQuotes {
+    fn code(&self) -> &'static str {
+        "F542"
+    }
+
+    fn name(&self) -> &'static str {
+        "fstring-nested-quotes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags f-strings that reuse their own quote character inside a replacement field, which needs Python 3.12+."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        if self.target_version >= NESTED_QUOTES_VERSION {
+            return Ok(Vec::new());
+        }
+
+        let chars: Vec<char> = context.source.chars().collect();
+        let mut scanner = Scanner { chars: &chars, nested: Vec::new() };
+        scanner.run();
+
+        Ok(scanner
+            .nested
+            .into_iter()
+            .map(|offset| {
+                let line = chars[..offset].iter().filter(|&&c| c == '\n').count() + 1;
+                let column = offset - chars[..offset].iter().rposition(|&c| c == '\n').map_or(0, |newline| newline + 1) + 1;
+                Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Nested same-type quotes in f-string require Python {}+", NESTED_QUOTES_VERSION),
+                    line,
+                    column,
+                    end_line: line,
+                    end_column: column,
+                    path: String::new(),
+                    code: "F542".to_string(),
+                }
+            })
+            .collect())
+    }
+}
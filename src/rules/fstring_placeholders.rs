@@ -0,0 +1,61 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct FStringMissingPlaceholders;
+
+struct FStringVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for FStringVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match &expr.node {
+            ast::ExprKind::JoinedStr { values } => {
+                let has_placeholder = values
+                    .iter()
+                    .any(|value| matches!(value.node, ast::ExprKind::FormattedValue { .. }));
+                if !has_placeholder {
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: "f-string without any placeholders".to_string(),
+                        line: expr.location.row(),
+                        column: expr.location.column() + 1,
+                        end_line: expr.location.row(),
+                        end_column: expr.location.column() + 1,
+                        path: String::new(),
+                        code: "F541".to_string(),
+                    });
+                }
+                visitor::walk_expr(self, expr);
+            }
+            // A format spec like `{x:>10}` is itself a JoinedStr of constants; only the value matters
+            ast::ExprKind::FormattedValue { value, .. } => {
+                self.visit_expr(value);
+            }
+            _ => visitor::walk_expr(self, expr),
+        }
+    }
+}
+
+impl super::Rule for FStringMissingPlaceholders {
+    fn code(&self) -> &'static str {
+        "F541"
+    }
+
+    fn name(&self) -> &'static str {
+        "fstring-missing-placeholders"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags f-strings that contain no placeholders."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = FStringVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct FunctionLength {
+    max_lines: usize,
+}
+
+impl FunctionLength {
+    pub fn new(max_lines: usize) -> Self {
+        Self { max_lines }
+    }
+}
+
+struct FunctionLengthVisitor {
+    max_lines: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The last line of a statement, found through its last nested statement
+/// rather than trusting the end location of the outer one.
+fn last_line(stmt: &ast::Stmt) -> usize {
+    let last_child = match &stmt.node {
+        ast::StmtKind::FunctionDef { body, .. }
+        | ast::StmtKind::AsyncFunctionDef { body, .. }
+        | ast::StmtKind::ClassDef { body, .. }
+        | ast::StmtKind::With { body, .. }
+        | ast::StmtKind::AsyncWith { body, .. } => body.last(),
+        ast::StmtKind::For { body, orelse, .. }
+        | ast::StmtKind::AsyncFor { body, orelse, .. }
+        | ast::StmtKind::While { body, orelse, .. }
+        | ast::StmtKind::If { body, orelse, .. } => orelse.last().or(body.last()),
+        ast::StmtKind::Try { body, handlers, orelse, finalbody } => finalbody
+            .last()
+            .or(orelse.last())
+            .or_else(|| {
+                handlers.last().and_then(|handler| {
+                    let ast::ExcepthandlerKind::ExceptHandler { body, .. } = &handler.node;
+                    body.last()
+                })
+            })
+            .or(body.last()),
+        _ => None,
+    };
+    let own = stmt.end_location.map_or(stmt.location.row(), |end| end.row());
+    last_child.map_or(own, |child| own.max(last_line(child)))
+}
+
+impl Visitor for FunctionLengthVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::FunctionDef { name, body, .. } | ast::StmtKind::AsyncFunctionDef { name, body, .. } = &stmt.node {
+            if let (Some(first), Some(last)) = (body.first(), body.last()) {
+                // The signature and decorators don't count, only the body
+                let lines = last_line(last) - first.location.row() + 1;
+                if lines > self.max_lines {
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: format!("Function '{}' is too long ({} > {} lines)", name, lines, self.max_lines),
+                        line: stmt.location.row(),
+                        column: stmt.location.column() + 1,
+                        end_line: stmt.location.row(),
+                        end_column: stmt.location.column() + 1,
+                        path: String::new(),
+                        code: "C902".to_string(),
+                    });
+                }
+            }
+        }
+        // Nested functions are measured on their own as well
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for FunctionLength {
+    fn code(&self) -> &'static str {
+        "C902"
+    }
+
+    fn name(&self) -> &'static str {
+        "function-too-long"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags functions whose body spans more lines than the configured maximum."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = FunctionLengthVisitor { max_lines: self.max_lines, diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
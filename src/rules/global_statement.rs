@@ -0,0 +1,67 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct GlobalStatement;
+
+#[derive(Default)]
+struct GlobalStatementVisitor {
+    diagnostics: Vec<Diagnostic>,
+    function_depth: usize,
+}
+
+impl Visitor for GlobalStatementVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } => {
+                self.function_depth += 1;
+                visitor::walk_stmt(self, stmt);
+                self.function_depth -= 1;
+                return;
+            }
+            // `global` at module level is a no-op; only declarations inside functions rebind anything
+            ast::StmtKind::Global { names } | ast::StmtKind::Nonlocal { names } if self.function_depth > 0 => {
+                let keyword = if matches!(stmt.node, ast::StmtKind::Global { .. }) { "global" } else { "nonlocal" };
+                let end = stmt.end_location.unwrap_or(stmt.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Use of '{}' for: {}", keyword, names.join(", ")),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column(),
+                    path: String::new(),
+                    code: "PLW0603".to_string(),
+                });
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for GlobalStatement {
+    fn code(&self) -> &'static str {
+        "PLW0603"
+    }
+
+    fn name(&self) -> &'static str {
+        "global-statement"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'global' and 'nonlocal' declarations inside functions."
+    }
+
+    fn default_enabled(&self) -> bool {
+        false
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = GlobalStatementVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
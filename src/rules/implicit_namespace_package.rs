@@ -0,0 +1,75 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use std::path::Path;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct ImplicitNamespacePackage;
+
+/// Whether the file at `path` sits in a regular package, i.e. its directory
+/// has an `__init__.py` (or a stub for one).
+pub(crate) fn in_package(path: &Path) -> bool {
+    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    dir.join("__init__.py").is_file() || dir.join("__init__.pyi").is_file()
+}
+
+/// Finds the first `from . import x` style import, wherever it is.
+#[derive(Default)]
+struct RelativeImportFinder {
+    /// Start and, if on the same line, end of the import.
+    found: Option<(ast::Location, ast::Location)>,
+}
+
+impl Visitor for RelativeImportFinder {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if self.found.is_some() {
+            return;
+        }
+        if let ast::StmtKind::ImportFrom { level: Some(level), .. } = &stmt.node {
+            if *level > 0 {
+                let end = stmt.end_location.filter(|end| end.row() == stmt.location.row()).unwrap_or(stmt.location);
+                self.found = Some((stmt.location, end));
+                return;
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for ImplicitNamespacePackage {
+    fn code(&self) -> &'static str {
+        "INP001"
+    }
+
+    fn name(&self) -> &'static str {
+        "implicit-namespace-package"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags files using relative imports in a directory without an '__init__.py', which isn't importable as a regular package."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        // Source without a file, e.g. from stdin, has no package to look at
+        if context.path.as_os_str().is_empty() || in_package(context.path) {
+            return Ok(Vec::new());
+        }
+        let mut finder = RelativeImportFinder::default();
+        visitor::walk_suite(&mut finder, context.ast);
+        Ok(finder
+            .found
+            .map(|(start, end)| Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "Relative import used but no __init__.py in package directory".to_string(),
+                line: start.row(),
+                column: start.column() + 1,
+                end_line: end.row(),
+                end_column: end.column().max(start.column() + 1),
+                path: String::new(),
+                code: "INP001".to_string(),
+            })
+            .into_iter()
+            .collect())
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct ImportOrder;
+
+/// Top-level modules of the Python 3 standard library, sorted for binary search.
+const STDLIB_MODULES: &[&str] = &[
+    "abc", "aifc", "antigravity", "argparse", "array", "ast", "asynchat", "asyncio", "asyncore",
+    "atexit", "audioop", "base64", "bdb", "binascii", "bisect", "builtins", "bz2", "cProfile",
+    "calendar", "cgi", "cgitb", "chunk", "cmath", "cmd", "code", "codecs", "codeop",
+    "collections", "colorsys", "compileall", "concurrent", "configparser", "contextlib",
+    "contextvars", "copy", "copyreg", "crypt", "csv", "ctypes", "curses", "dataclasses",
+    "datetime", "dbm", "decimal", "difflib", "dis", "distutils", "doctest", "email",
+    "encodings", "ensurepip", "enum", "errno", "faulthandler", "fcntl", "filecmp", "fileinput",
+    "fnmatch", "fractions", "ftplib", "functools", "gc", "genericpath", "getopt", "getpass",
+    "gettext", "glob", "graphlib", "grp", "gzip", "hashlib", "heapq", "hmac", "html", "http",
+    "idlelib", "imaplib", "imghdr", "imp", "importlib", "inspect", "io", "ipaddress",
+    "itertools", "json", "keyword", "lib2to3", "linecache", "locale", "logging", "lzma",
+    "mailbox", "mailcap", "marshal", "math", "mimetypes", "mmap", "modulefinder", "msilib",
+    "msvcrt", "multiprocessing", "netrc", "nis", "nntplib", "nt", "ntpath", "nturl2path",
+    "numbers", "opcode", "operator", "optparse", "os", "ossaudiodev", "pathlib", "pdb",
+    "pickle", "pickletools", "pipes", "pkgutil", "platform", "plistlib", "poplib", "posix",
+    "posixpath", "pprint", "profile", "pstats", "pty", "pwd", "py_compile", "pyclbr", "pydoc",
+    "pydoc_data", "pyexpat", "queue", "quopri", "random", "re", "readline", "reprlib",
+    "resource", "rlcompleter", "runpy", "sched", "secrets", "select", "selectors", "shelve",
+    "shlex", "shutil", "signal", "site", "smtpd", "smtplib", "sndhdr", "socket", "socketserver",
+    "spwd", "sqlite3", "sre_compile", "sre_constants", "sre_parse", "ssl", "stat", "statistics",
+    "string", "stringprep", "struct", "subprocess", "sunau", "symtable", "sys", "sysconfig",
+    "syslog", "tabnanny", "tarfile", "telnetlib", "tempfile", "termios", "textwrap", "this",
+    "threading", "time", "timeit", "tkinter", "token", "tokenize", "tomllib", "trace",
+    "traceback", "tracemalloc", "tty", "turtle", "turtledemo", "types", "typing", "unicodedata",
+    "unittest", "urllib", "uu", "uuid", "venv", "warnings", "wave", "weakref", "webbrowser",
+    "winreg", "winsound", "wsgiref", "xdrlib", "xml", "xmlrpc", "zipapp", "zipfile",
+    "zipimport", "zlib", "zoneinfo",
+];
+
+/// Import sections in the order they must appear, like isort's defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Future,
+    Stdlib,
+    ThirdParty,
+    Local,
+}
+
+/// Sort key for an import statement: section, then plain `import` before
+/// `from ... import`, then module name ignoring case.
+fn sort_key(stmt: &ast::Stmt) -> Option<(Section, bool, String)> {
+    let (module, level, is_from) = match &stmt.node {
+        ast::StmtKind::Import { names } => (names.first()?.node.name.as_str(), 0, false),
+        ast::StmtKind::ImportFrom { module, level, .. } => {
+            (module.as_deref().unwrap_or(""), level.unwrap_or(0), true)
+        }
+        _ => return None,
+    };
+
+    let top_level = module.split('.').next().unwrap_or(module);
+    let section = if level > 0 {
+        Section::Local
+    } else if top_level == "__future__" {
+        Section::Future
+    } else if STDLIB_MODULES.binary_search(&top_level).is_ok() {
+        Section::Stdlib
+    } else {
+        Section::ThirdParty
+    };
+
+    Some((section, is_from, format!("{}{}", ".".repeat(level), module.to_lowercase())))
+}
+
+impl super::Rule for ImportOrder {
+    fn code(&self) -> &'static str {
+        "I001"
+    }
+
+    fn name(&self) -> &'static str {
+        "unsorted-imports"
+    }
+
+    fn description(&self) -> &'static str {
+        "Expects top-level imports grouped as standard library, third-party then local, and alphabetized within each group."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut previous: Option<(Section, bool, String)> = None;
+
+        for stmt in context.ast {
+            // Comments never reach the AST, but any other statement starts a new block
+            let Some(key) = sort_key(stmt) else {
+                previous = None;
+                continue;
+            };
+            if previous.as_ref().is_some_and(|previous| key < *previous) {
+                return Ok(vec![Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "Imports are not sorted".to_string(),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: stmt.location.row(),
+                    end_column: stmt.location.column() + 1,
+                    path: String::new(),
+                    code: "I001".to_string(),
+                }]);
+            }
+            previous = Some(key);
+        }
+
+        Ok(Vec::new())
+    }
+}
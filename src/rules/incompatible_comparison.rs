@@ -0,0 +1,90 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct IncompatibleComparison;
+
+struct IncompatibleComparisonVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The type of a literal operand, if it's certain. Only constants and empty
+/// collection displays count; `None` is left to the `E711` rule.
+fn literal_type(expr: &ast::Expr) -> Option<&'static str> {
+    match &expr.node {
+        ast::ExprKind::Constant { value, .. } => match value {
+            ast::Constant::Str(_) => Some("str"),
+            ast::Constant::Bytes(_) => Some("bytes"),
+            ast::Constant::Int(_) => Some("int"),
+            ast::Constant::Float(_) => Some("float"),
+            ast::Constant::Complex { .. } => Some("complex"),
+            ast::Constant::Bool(_) => Some("bool"),
+            _ => None,
+        },
+        ast::ExprKind::List { elts, .. } if elts.is_empty() => Some("list"),
+        ast::ExprKind::Tuple { elts, .. } if elts.is_empty() => Some("tuple"),
+        ast::ExprKind::Dict { keys, .. } if keys.is_empty() => Some("dict"),
+        _ => None,
+    }
+}
+
+/// Numbers of different types still compare equal, e.g. `1 == 1.0 == True`.
+fn is_number(type_name: &str) -> bool {
+    matches!(type_name, "int" | "float" | "complex" | "bool")
+}
+
+impl Visitor for IncompatibleComparisonVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            let operands: Vec<&ast::Expr> = std::iter::once(left.as_ref()).chain(comparators).collect();
+            for (i, op) in ops.iter().enumerate() {
+                let result = match op {
+                    ast::Cmpop::Eq => "False",
+                    ast::Cmpop::NotEq => "True",
+                    _ => continue,
+                };
+                let (lhs, rhs) = (operands[i], operands[i + 1]);
+                let (Some(lhs_type), Some(rhs_type)) = (literal_type(lhs), literal_type(rhs)) else {
+                    continue;
+                };
+                if lhs_type == rhs_type || (is_number(lhs_type) && is_number(rhs_type)) {
+                    continue;
+                }
+                let end = rhs.end_location.filter(|end| end.row() == lhs.location.row()).unwrap_or(lhs.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Comparison between {} and {} is always {}", lhs_type, rhs_type, result),
+                    line: lhs.location.row(),
+                    column: lhs.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(lhs.location.column() + 1),
+                    path: String::new(),
+                    code: "W0136".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for IncompatibleComparison {
+    fn code(&self) -> &'static str {
+        "W0136"
+    }
+
+    fn name(&self) -> &'static str {
+        "incompatible-comparison"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags '==' and '!=' between literals of unrelated types, such as a str and an int, whose result never changes."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = IncompatibleComparisonVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct InconsistentReturns;
+
+struct InconsistentReturnsVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The `return` statements and `yield`s of one function body, not counting
+/// those of nested functions, classes or lambdas.
+#[derive(Default)]
+struct ReturnCollector {
+    with_value: usize,
+    bare: usize,
+    has_yield: bool,
+}
+
+impl Visitor for ReturnCollector {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } | ast::StmtKind::ClassDef { .. } => {}
+            ast::StmtKind::Return { value: Some(value) } => {
+                self.with_value += 1;
+                self.visit_expr(value);
+            }
+            ast::StmtKind::Return { value: None } => self.bare += 1,
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match &expr.node {
+            ast::ExprKind::Lambda { .. } => {}
+            ast::ExprKind::Yield { .. } | ast::ExprKind::YieldFrom { .. } => self.has_yield = true,
+            _ => visitor::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Whether a loop body contains a `break` for that loop, i.e. outside nested loops.
+fn breaks(body: &[ast::Stmt]) -> bool {
+    body.iter().any(|stmt| match &stmt.node {
+        ast::StmtKind::Break => true,
+        ast::StmtKind::If { body, orelse, .. } => breaks(body) || breaks(orelse),
+        ast::StmtKind::With { body, .. } | ast::StmtKind::AsyncWith { body, .. } => breaks(body),
+        ast::StmtKind::Try { body, handlers, orelse, finalbody } => {
+            breaks(body)
+                || breaks(orelse)
+                || breaks(finalbody)
+                || handlers.iter().any(|handler| {
+                    let ast::ExcepthandlerKind::ExceptHandler { body, .. } = &handler.node;
+                    breaks(body)
+                })
+        }
+        _ => false,
+    })
+}
+
+/// Whether control can never run past the end of `body`, because every path
+/// ends in a `return`, a `raise` or a `while True` loop without a `break`.
+fn always_exits(body: &[ast::Stmt]) -> bool {
+    let Some(last) = body.last() else {
+        return false;
+    };
+    match &last.node {
+        ast::StmtKind::Return { .. } | ast::StmtKind::Raise { .. } => true,
+        ast::StmtKind::If { body, orelse, .. } => always_exits(body) && always_exits(orelse),
+        ast::StmtKind::With { body, .. } | ast::StmtKind::AsyncWith { body, .. } => always_exits(body),
+        ast::StmtKind::While { test, body, .. } => {
+            matches!(&test.node, ast::ExprKind::Constant { value: ast::Constant::Bool(true), .. }) && !breaks(body)
+        }
+        ast::StmtKind::Try { body, handlers, orelse, finalbody } => {
+            always_exits(finalbody)
+                || ((always_exits(body) || always_exits(orelse))
+                    && handlers.iter().all(|handler| {
+                        let ast::ExcepthandlerKind::ExceptHandler { body, .. } = &handler.node;
+                        always_exits(body)
+                    }))
+        }
+        _ => false,
+    }
+}
+
+impl Visitor for InconsistentReturnsVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::FunctionDef { name, body, .. } | ast::StmtKind::AsyncFunctionDef { name, body, .. } = &stmt.node {
+            let mut returns = ReturnCollector::default();
+            visitor::walk_suite(&mut returns, body);
+            // Falling off the end is an implicit bare `return`
+            let bare = returns.bare > 0 || !always_exits(body);
+            if returns.with_value > 0 && bare && !returns.has_yield {
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Function '{}' has inconsistent return statements (some with value, some without)", name),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: stmt.location.row(),
+                    end_column: stmt.location.column() + 1,
+                    path: String::new(),
+                    code: "R1710".to_string(),
+                });
+            }
+        }
+        // Nested functions are checked on their own
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for InconsistentReturns {
+    fn code(&self) -> &'static str {
+        "R1710"
+    }
+
+    fn name(&self) -> &'static str {
+        "inconsistent-return-statements"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags functions that return a value on some paths but return nothing on others."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = InconsistentReturnsVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+use std::collections::HashSet;
+
+pub struct Indentation {
+    indent_size: usize,
+    use_tabs: bool,
+}
+
+impl Indentation {
+    pub fn new(indent_size: usize, use_tabs: bool) -> Self {
+        Self { indent_size: indent_size.max(1), use_tabs }
+    }
+}
+
+/// Collects the lines that continue a statement begun on an earlier line:
+/// the rest of a bracketed or multi-line expression (including lines inside
+/// a triple-quoted string) and the header of a compound statement.
+#[derive(Default)]
+struct ContinuationLines {
+    lines: HashSet<usize>,
+}
+
+impl ContinuationLines {
+    fn add_span(&mut self, start: &ast::Location, end: Option<&ast::Location>) {
+        if let Some(end) = end {
+            self.lines.extend(start.row() + 1..=end.row());
+        }
+    }
+}
+
+impl Visitor for ContinuationLines {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        let body = match &stmt.node {
+            ast::StmtKind::FunctionDef { body, .. }
+            | ast::StmtKind::AsyncFunctionDef { body, .. }
+            | ast::StmtKind::ClassDef { body, .. }
+            | ast::StmtKind::For { body, .. }
+            | ast::StmtKind::AsyncFor { body, .. }
+            | ast::StmtKind::While { body, .. }
+            | ast::StmtKind::If { body, .. }
+            | ast::StmtKind::With { body, .. }
+            | ast::StmtKind::AsyncWith { body, .. }
+            | ast::StmtKind::Try { body, .. } => Some(body),
+            _ => None,
+        };
+        match body.and_then(|body| body.first()) {
+            // The header runs from the keyword up to the line before the body
+            Some(first) => self.lines.extend(stmt.location.row() + 1..first.location.row()),
+            None => self.add_span(&stmt.location, stmt.end_location.as_ref()),
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        self.add_span(&expr.location, expr.end_location.as_ref());
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for Indentation {
+    fn code(&self) -> &'static str {
+        "E111"
+    }
+
+    fn name(&self) -> &'static str {
+        "indentation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags space indentation that isn't a multiple of the configured indent size."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        // Tab-indented projects are checked by W191 instead
+        if self.use_tabs {
+            return Ok(Vec::new());
+        }
+
+        // Continuation lines may be aligned freely, e.g. with an opening bracket
+        let mut continuations = ContinuationLines::default();
+        visitor::walk_suite(&mut continuations, context.ast);
+
+        let mut diagnostics = Vec::new();
+        for (i, line) in context.lines.iter().enumerate() {
+            let line_num = i + 1;
+            let spaces = line.chars().take_while(|&c| c == ' ').count();
+            if spaces == 0 || spaces % self.indent_size == 0 || line.trim().is_empty() || continuations.lines.contains(&line_num) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: format!("Indentation of {} spaces should be a multiple of {}", spaces, self.indent_size),
+                line: line_num,
+                column: 1,
+                end_line: line_num,
+                end_column: 1,
+                path: String::new(),
+                code: "E111".to_string(),
+            });
+        }
+
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct IsLiteral;
+
+struct IsLiteralVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether `expr` is a literal whose identity isn't guaranteed. `None`, `True`,
+/// `False` and `...` are singletons, so comparing them with `is` is fine.
+fn is_literal(expr: &ast::Expr) -> bool {
+    match &expr.node {
+        ast::ExprKind::Constant { value, .. } => {
+            !matches!(value, ast::Constant::None | ast::Constant::Bool(_) | ast::Constant::Ellipsis)
+        }
+        ast::ExprKind::Tuple { elts, .. } => elts.iter().all(is_literal),
+        ast::ExprKind::List { .. } | ast::ExprKind::Dict { .. } | ast::ExprKind::Set { .. } => true,
+        ast::ExprKind::JoinedStr { .. } => true,
+        _ => false,
+    }
+}
+
+impl Visitor for IsLiteralVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            // In a chain like `a is 1 is b`, ops[i] sits between operands[i] and operands[i + 1]
+            let operands: Vec<&ast::Expr> = std::iter::once(left.as_ref()).chain(comparators).collect();
+            for (i, op) in ops.iter().enumerate() {
+                let (lhs, rhs) = (operands[i], operands[i + 1]);
+                let message = match op {
+                    ast::Cmpop::Is => "Use '==' to compare with a literal, not 'is'",
+                    ast::Cmpop::IsNot => "Use '!=' to compare with a literal, not 'is not'",
+                    _ => continue,
+                };
+                if !is_literal(lhs) && !is_literal(rhs) {
+                    continue;
+                }
+                // Span the whole comparison when it fits on one line
+                let end = rhs.end_location.filter(|end| end.row() == lhs.location.row()).unwrap_or(lhs.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: message.to_string(),
+                    line: lhs.location.row(),
+                    column: lhs.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(lhs.location.column() + 1),
+                    path: String::new(),
+                    code: "F632".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for IsLiteral {
+    fn code(&self) -> &'static str {
+        "F632"
+    }
+
+    fn name(&self) -> &'static str {
+        "is-literal"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'is' and 'is not' comparisons against str, bytes, number and container literals."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = IsLiteralVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct LambdaAssignment;
+
+struct LambdaAssignmentVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for LambdaAssignmentVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        let assigned = match &stmt.node {
+            ast::StmtKind::Assign { targets, value, .. } if targets.len() == 1 => Some((&targets[0], value)),
+            ast::StmtKind::AnnAssign { target, value: Some(value), .. } => Some((target.as_ref(), value)),
+            _ => None,
+        };
+        if let Some((target, value)) = assigned {
+            if matches!(target.node, ast::ExprKind::Name { .. })
+                && matches!(value.node, ast::ExprKind::Lambda { .. })
+            {
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "Do not assign a lambda expression, use a def".to_string(),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: stmt.location.row(),
+                    end_column: stmt.location.column() + 1,
+                    path: String::new(),
+                    code: "E731".to_string(),
+                });
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for LambdaAssignment {
+    fn code(&self) -> &'static str {
+        "E731"
+    }
+
+    fn name(&self) -> &'static str {
+        "lambda-assignment"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags lambda expressions assigned to a name, which should be written as a def."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = LambdaAssignmentVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct LenComparison;
+
+/// Finds out whether the module rebinds `len`, in which case calls to it
+/// can't be assumed to be the builtin.
+#[derive(Default)]
+struct LenBinding {
+    shadowed: bool,
+}
+
+impl LenBinding {
+    fn check_target(&mut self, target: &ast::Expr) {
+        match &target.node {
+            ast::ExprKind::Name { id, .. } => self.shadowed |= id == "len",
+            ast::ExprKind::Tuple { elts, .. } | ast::ExprKind::List { elts, .. } => {
+                for elt in elts {
+                    self.check_target(elt);
+                }
+            }
+            ast::ExprKind::Starred { value, .. } => self.check_target(value),
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for LenBinding {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, args, .. } | ast::StmtKind::AsyncFunctionDef { name, args, .. } => {
+                self.shadowed |= name == "len";
+                self.shadowed |= args
+                    .posonlyargs
+                    .iter()
+                    .chain(&args.args)
+                    .chain(&args.kwonlyargs)
+                    .chain(args.vararg.as_deref())
+                    .chain(args.kwarg.as_deref())
+                    .any(|arg| arg.node.arg == "len");
+            }
+            ast::StmtKind::ClassDef { name, .. } => self.shadowed |= name == "len",
+            ast::StmtKind::Assign { targets, .. } => {
+                for target in targets {
+                    self.check_target(target);
+                }
+            }
+            ast::StmtKind::AnnAssign { target, .. }
+            | ast::StmtKind::AugAssign { target, .. }
+            | ast::StmtKind::For { target, .. }
+            | ast::StmtKind::AsyncFor { target, .. } => self.check_target(target),
+            ast::StmtKind::Import { names } | ast::StmtKind::ImportFrom { names, .. } => {
+                self.shadowed |= names.iter().any(|alias| {
+                    alias.node.asname.as_deref().unwrap_or(&alias.node.name) == "len"
+                });
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+struct LenComparisonVisitor<'a> {
+    source: &'a str,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The argument of a single-argument `len(...)` call.
+fn len_argument(expr: &ast::Expr) -> Option<&ast::Expr> {
+    match &expr.node {
+        ast::ExprKind::Call { func, args, keywords } if args.len() == 1 && keywords.is_empty() => {
+            match &func.node {
+                ast::ExprKind::Name { id, .. } if id == "len" => Some(&args[0]),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn int_value(expr: &ast::Expr) -> Option<i64> {
+    match &expr.node {
+        ast::ExprKind::Constant { value: ast::Constant::Int(value), .. } => value.try_into().ok(),
+        _ => None,
+    }
+}
+
+impl LenComparisonVisitor<'_> {
+    /// Source text of a single-line expression, or `x` as a placeholder.
+    fn text(&self, expr: &ast::Expr) -> String {
+        super::expr_source(self.source, expr).unwrap_or("x").to_string()
+    }
+}
+
+impl Visitor for LenComparisonVisitor<'_> {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        // Chains like `0 < len(x) < 5` mean something else, so only plain comparisons
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            if let ([op], [right]) = (ops.as_slice(), comparators.as_slice()) {
+                // `len(x) == 0` is true when the container is empty
+                let empty_when = match (len_argument(left), int_value(right), op) {
+                    (Some(arg), Some(0), ast::Cmpop::Eq) | (Some(arg), Some(1), ast::Cmpop::Lt) => Some((arg, true)),
+                    (Some(arg), Some(0), ast::Cmpop::NotEq | ast::Cmpop::Gt) => Some((arg, false)),
+                    _ => None,
+                };
+                if let Some((arg, empty)) = empty_when {
+                    let name = self.text(arg);
+                    let suggestion = if empty { format!("not {}", name) } else { name };
+                    let end = expr.end_location.unwrap_or(expr.location);
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: format!("Use '{}' instead of '{}'", suggestion, self.text(expr)),
+                        line: expr.location.row(),
+                        column: expr.location.column() + 1,
+                        end_line: end.row(),
+                        end_column: end.column().max(expr.location.column() + 1),
+                        path: String::new(),
+                        code: "PLC1802".to_string(),
+                    });
+                }
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for LenComparison {
+    fn code(&self) -> &'static str {
+        "PLC1802"
+    }
+
+    fn name(&self) -> &'static str {
+        "len-test"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags comparing len(x) against 0 where a truthiness check like 'not x' would do."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut binding = LenBinding::default();
+        visitor::walk_suite(&mut binding, context.ast);
+        if binding.shadowed {
+            return Ok(Vec::new());
+        }
+
+        let mut visitor = LenComparisonVisitor { source: context.source, diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
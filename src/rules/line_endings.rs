@@ -0,0 +1,96 @@
+use anyhow::Result;
+use crate::config::LineEnding;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct LineEndings {
+    expected: LineEnding,
+}
+
+impl LineEndings {
+    pub fn new(expected: LineEnding) -> Self {
+        Self { expected }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Ending {
+    Lf,
+    Crlf,
+    Cr,
+}
+
+impl Ending {
+    fn name(self) -> &'static str {
+        match self {
+            Ending::Lf => "LF",
+            Ending::Crlf => "CRLF",
+            Ending::Cr => "CR",
+        }
+    }
+}
+
+/// The ending of every line in `source`, in order. `str::lines` hides these,
+/// so the raw characters are scanned instead.
+fn line_endings(source: &str) -> Vec<Ending> {
+    let mut endings = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => endings.push(Ending::Lf),
+            '\r' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                endings.push(Ending::Crlf);
+            }
+            '\r' => endings.push(Ending::Cr),
+            _ => {}
+        }
+    }
+    endings
+}
+
+impl super::Rule for LineEndings {
+    fn code(&self) -> &'static str {
+        "E903"
+    }
+
+    fn name(&self) -> &'static str {
+        "line-endings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags files that mix line endings or don't use the configured line ending."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let endings = line_endings(context.source);
+        let Some(&first) = endings.first() else {
+            return Ok(Vec::new());
+        };
+
+        // Reported once per file, at the first line that breaks the expectation
+        let (expected, message) = match self.expected {
+            LineEnding::Lf => (Ending::Lf, None),
+            LineEnding::Crlf => (Ending::Crlf, None),
+            LineEnding::Auto => (first, Some("Mixed line endings")),
+        };
+        let Some((index, &found)) = endings.iter().enumerate().find(|(_, &e)| e != expected) else {
+            return Ok(Vec::new());
+        };
+
+        let message = match message {
+            Some(message) => format!("{} ({} and {})", message, expected.name(), found.name()),
+            None => format!("Expected {} line endings, found {}", expected.name(), found.name()),
+        };
+        Ok(vec![Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message,
+            line: index + 1,
+            column: 1,
+            end_line: index + 1,
+            end_column: 1,
+            path: String::new(),
+            code: "E903".to_string(),
+        }])
+    }
+}
@@ -1,24 +1,75 @@
 use anyhow::Result;
 use rustpython_parser::ast;
-use crate::linter::Diagnostic;
+use crate::linter::{Diagnostic, LintContext};
 use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+use std::collections::HashMap;
 
 pub struct LineLength {
     max_length: usize,
+    ignore_long_strings: bool,
 }
 
 impl LineLength {
     pub fn new(max_length: usize) -> Self {
-        Self { max_length }
+        Self { max_length, ignore_long_strings: false }
+    }
+
+    /// Skip lines that are only too long because of a string literal, such as
+    /// a long URL in a docstring.
+    pub fn with_ignore_long_strings(mut self, ignore_long_strings: bool) -> Self {
+        self.ignore_long_strings = ignore_long_strings;
+        self
+    }
+}
+
+/// Counts the characters of each line that belong to a string literal,
+/// including f-strings.
+struct StringColumns<'a> {
+    lines: &'a [&'a str],
+    covered: HashMap<usize, usize>,
+}
+
+impl Visitor for StringColumns<'_> {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match &expr.node {
+            ast::ExprKind::Constant { value: ast::Constant::Str(_), .. } | ast::ExprKind::JoinedStr { .. } => {
+                let end = expr.end_location.unwrap_or(expr.location);
+                for row in expr.location.row()..=end.row() {
+                    let length = self.lines.get(row - 1).map_or(0, |line| line.chars().count());
+                    let start = if row == expr.location.row() { expr.location.column() } else { 0 };
+                    let end = if row == end.row() { end.column().min(length) } else { length };
+                    *self.covered.entry(row).or_default() += end.saturating_sub(start);
+                }
+            }
+            _ => visitor::walk_expr(self, expr),
+        }
     }
 }
 
 impl super::Rule for LineLength {
-    fn check(&self, _ast: &ast::Suite, source: &str) -> Result<Vec<Diagnostic>> {
+    fn code(&self) -> &'static str {
+        "E501"
+    }
+
+    fn name(&self) -> &'static str {
+        "line-too-long"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags lines longer than the configured maximum line length."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
         let mut diagnostics = Vec::new();
-        let lines: Vec<&str> = source.lines().collect();
 
-        for (i, &line) in lines.iter().enumerate() {
+        // Characters of each line inside a string literal
+        let mut strings = StringColumns { lines: &context.lines, covered: HashMap::new() };
+        if self.ignore_long_strings {
+            visitor::walk_suite(&mut strings, context.ast);
+        }
+
+        for (i, &line) in context.lines.iter().enumerate() {
             let line_num = i + 1;
             let line_length = line.chars().count();
 
@@ -27,6 +78,12 @@ impl super::Rule for LineLength {
                 continue;
             }
 
+            // A line is exempt only if its strings are what push it past the limit
+            let covered = strings.covered.get(&line_num).copied().unwrap_or(0);
+            if covered > 0 && line_length - covered.min(line_length) <= self.max_length {
+                continue;
+            }
+
             if line_length > self.max_length {
                 diagnostics.push(Diagnostic {
                     level: DiagnosticLevel::Warning,
@@ -37,7 +94,10 @@ impl super::Rule for LineLength {
                     ),
                     line: line_num,
                     column: self.max_length + 1,
+                    end_line: line_num,
+                    end_column: line_length,
                     path: String::new(),
+                    code: "E501".to_string(),
                 });
             }
         }
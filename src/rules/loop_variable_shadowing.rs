@@ -0,0 +1,205 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+use std::collections::HashSet;
+
+pub struct LoopVariableShadowing;
+
+/// What one function scope (or the module) binds, as far as nested code sees it.
+#[derive(Default)]
+struct Scope {
+    params: HashSet<String>,
+    locals: HashSet<String>,
+    /// Targets of the loops and comprehensions currently being walked.
+    loops: Vec<String>,
+}
+
+/// Tracks the enclosing scopes while walking, innermost last.
+struct ShadowingVisitor {
+    scopes: Vec<Scope>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Records the names a function body binds, without descending into nested scopes.
+#[derive(Default)]
+struct LocalBindings {
+    names: HashSet<String>,
+}
+
+impl Visitor for LocalBindings {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, .. }
+            | ast::StmtKind::AsyncFunctionDef { name, .. }
+            | ast::StmtKind::ClassDef { name, .. } => {
+                self.names.insert(name.to_string());
+                return;
+            }
+            ast::StmtKind::Import { names } | ast::StmtKind::ImportFrom { names, .. } => {
+                for alias in names {
+                    let bound = alias.node.asname.as_deref().unwrap_or(&alias.node.name);
+                    self.names.insert(bound.split('.').next().unwrap_or(bound).to_string());
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        match &expr.node {
+            ast::ExprKind::Name { id, ctx: ast::ExprContext::Store } => {
+                self.names.insert(id.to_string());
+            }
+            // These bind their targets in a scope of their own
+            ast::ExprKind::Lambda { .. }
+            | ast::ExprKind::ListComp { .. }
+            | ast::ExprKind::SetComp { .. }
+            | ast::ExprKind::DictComp { .. }
+            | ast::ExprKind::GeneratorExp { .. } => return,
+            _ => {}
+        }
+        visitor::walk_expr(self, expr);
+    }
+
+    fn visit_excepthandler(&mut self, handler: &ast::Excepthandler) {
+        let ast::ExcepthandlerKind::ExceptHandler { name, .. } = &handler.node;
+        self.names.extend(name.clone());
+        visitor::walk_excepthandler(self, handler);
+    }
+}
+
+/// The names a `for` or comprehension target binds, with their expressions.
+fn target_names(target: &ast::Expr) -> Vec<(&str, &ast::Expr)> {
+    match &target.node {
+        ast::ExprKind::Name { id, .. } => vec![(id.as_str(), target)],
+        ast::ExprKind::Tuple { elts, .. } | ast::ExprKind::List { elts, .. } => {
+            elts.iter().flat_map(target_names).collect()
+        }
+        ast::ExprKind::Starred { value, .. } => target_names(value),
+        _ => Vec::new(),
+    }
+}
+
+fn parameter_names(args: &ast::Arguments) -> HashSet<String> {
+    args.posonlyargs
+        .iter()
+        .chain(&args.args)
+        .chain(args.vararg.as_deref())
+        .chain(&args.kwonlyargs)
+        .chain(args.kwarg.as_deref())
+        .map(|arg| arg.node.arg.to_string())
+        .collect()
+}
+
+impl ShadowingVisitor {
+    /// Whether `name` is already bound by the current function's parameters, an
+    /// enclosing loop, or anything an enclosing function binds. Module-level
+    /// names aren't counted; reusing them in a function is too common to flag.
+    fn is_outer(&self, name: &str) -> bool {
+        let Some((current, enclosing)) = self.scopes.split_last() else {
+            return false;
+        };
+        current.params.contains(name)
+            || current.loops.iter().any(|bound| bound == name)
+            || enclosing
+                .iter()
+                .any(|scope| scope.params.contains(name) || scope.locals.contains(name))
+    }
+
+    /// Reports target names that shadow an outer binding, then marks them as
+    /// bound for whatever is walked before the matching [`Self::pop_loop`].
+    fn push_loop(&mut self, target: &ast::Expr) -> usize {
+        let names = target_names(target);
+        for &(name, expr) in &names {
+            if self.is_outer(name) {
+                let end = expr.end_location.unwrap_or(expr.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Loop variable '{}' shadows an outer binding", name),
+                    line: expr.location.row(),
+                    column: expr.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(expr.location.column() + 1),
+                    path: String::new(),
+                    code: "W0621".to_string(),
+                });
+            }
+        }
+        let scope = self.scopes.last_mut().expect("the module scope is never popped");
+        scope.loops.extend(names.iter().map(|(name, _)| name.to_string()));
+        names.len()
+    }
+
+    fn pop_loop(&mut self, count: usize) {
+        let scope = self.scopes.last_mut().expect("the module scope is never popped");
+        scope.loops.truncate(scope.loops.len() - count);
+    }
+}
+
+impl Visitor for ShadowingVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { args, body, .. } | ast::StmtKind::AsyncFunctionDef { args, body, .. } => {
+                let mut locals = LocalBindings::default();
+                visitor::walk_suite(&mut locals, body);
+                self.scopes.push(Scope { params: parameter_names(args), locals: locals.names, loops: Vec::new() });
+                visitor::walk_stmt(self, stmt);
+                self.scopes.pop();
+            }
+            ast::StmtKind::For { target, iter, body, orelse, .. }
+            | ast::StmtKind::AsyncFor { target, iter, body, orelse, .. } => {
+                self.visit_expr(iter);
+                let count = self.push_loop(target);
+                visitor::walk_suite(self, body);
+                self.pop_loop(count);
+                visitor::walk_suite(self, orelse);
+            }
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        let (generators, elements): (_, Vec<&ast::Expr>) = match &expr.node {
+            ast::ExprKind::ListComp { elt, generators }
+            | ast::ExprKind::SetComp { elt, generators }
+            | ast::ExprKind::GeneratorExp { elt, generators } => (generators, vec![elt]),
+            ast::ExprKind::DictComp { key, value, generators } => (generators, vec![key, value]),
+            _ => return visitor::walk_expr(self, expr),
+        };
+        let mut count = 0;
+        for generator in generators {
+            self.visit_expr(&generator.iter);
+            count += self.push_loop(&generator.target);
+            for condition in &generator.ifs {
+                self.visit_expr(condition);
+            }
+        }
+        for element in elements {
+            self.visit_expr(element);
+        }
+        self.pop_loop(count);
+    }
+}
+
+impl super::Rule for LoopVariableShadowing {
+    fn code(&self) -> &'static str {
+        "W0621"
+    }
+
+    fn name(&self) -> &'static str {
+        "redefined-outer-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags loop and comprehension variables that reuse a parameter, an enclosing loop's variable or a name from an enclosing function."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = ShadowingVisitor { scopes: vec![Scope::default()], diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
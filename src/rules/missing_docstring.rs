@@ -0,0 +1,89 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct MissingDocstring;
+
+/// Whether the first statement of `body` is a string literal.
+fn has_docstring(body: &[ast::Stmt]) -> bool {
+    body.first().is_some_and(|stmt| match &stmt.node {
+        ast::StmtKind::Expr { value } => {
+            matches!(&value.node, ast::ExprKind::Constant { value: ast::Constant::Str(_), .. })
+        }
+        _ => false,
+    })
+}
+
+fn missing(kind: &str, location: &ast::Location) -> Diagnostic {
+    Diagnostic {
+        level: DiagnosticLevel::Warning,
+        message: format!("Missing docstring for public {}", kind),
+        line: location.row(),
+        column: location.column() + 1,
+        end_line: location.row(),
+        end_column: location.column() + 1,
+        path: String::new(),
+        code: "D100".to_string(),
+    }
+}
+
+/// Checks the public functions and classes in `body`, descending into class
+/// bodies for methods and into `if`/`try`/`with`/loop blocks, such as an
+/// `if TYPE_CHECKING:` guard. Functions nested in functions aren't public API.
+fn check_body(body: &[ast::Stmt], in_class: bool, diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in body {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, body, .. } | ast::StmtKind::AsyncFunctionDef { name, body, .. } => {
+                if !name.starts_with('_') && !has_docstring(body) {
+                    let kind = if in_class { "method" } else { "function" };
+                    diagnostics.push(missing(&format!("{} '{}'", kind, name), &stmt.location));
+                }
+            }
+            ast::StmtKind::ClassDef { name, body, .. } => {
+                if name.starts_with('_') {
+                    continue;
+                }
+                if !has_docstring(body) {
+                    diagnostics.push(missing(&format!("class '{}'", name), &stmt.location));
+                }
+                check_body(body, true, diagnostics);
+            }
+            _ => {
+                for nested in super::nested_blocks(stmt) {
+                    check_body(nested, in_class, diagnostics);
+                }
+            }
+        }
+    }
+}
+
+impl super::Rule for MissingDocstring {
+    fn code(&self) -> &'static str {
+        "D100"
+    }
+
+    fn name(&self) -> &'static str {
+        "missing-docstring"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags public modules, classes, methods and functions without a docstring. Private names and functions nested in functions are skipped."
+    }
+
+    fn default_enabled(&self) -> bool {
+        false
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        // An empty suite is also what we get for files that failed to parse
+        if let Some(first) = context.ast.first() {
+            if !has_docstring(context.ast) {
+                diagnostics.push(missing("module", &first.location));
+            }
+        }
+        check_body(context.ast, false, &mut diagnostics);
+        Ok(diagnostics)
+    }
+}
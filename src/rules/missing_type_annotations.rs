@@ -0,0 +1,95 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct MissingTypeAnnotations;
+
+fn missing(message: String, location: &ast::Location, end: Option<ast::Location>) -> Diagnostic {
+    let end = end.filter(|end| end.row() == location.row()).unwrap_or(*location);
+    Diagnostic {
+        level: DiagnosticLevel::Warning,
+        message,
+        line: location.row(),
+        column: location.column() + 1,
+        end_line: end.row(),
+        end_column: end.column().max(location.column() + 1),
+        path: String::new(),
+        code: "ANN001".to_string(),
+    }
+}
+
+fn check_function(
+    stmt: &ast::Stmt,
+    name: &str,
+    args: &ast::Arguments,
+    returns: Option<&ast::Expr>,
+    in_class: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let positional = args.posonlyargs.iter().chain(&args.args);
+    let params = positional
+        .enumerate()
+        // The receiver of a method is typed implicitly
+        .filter(|(index, arg)| !(in_class && *index == 0 && (arg.node.arg == "self" || arg.node.arg == "cls")))
+        .map(|(_, arg)| arg)
+        .chain(args.vararg.as_deref())
+        .chain(&args.kwonlyargs)
+        .chain(args.kwarg.as_deref());
+    for arg in params {
+        if arg.node.annotation.is_none() {
+            diagnostics.push(missing(
+                format!("Parameter '{}' of '{}' is missing a type annotation", arg.node.arg, name),
+                &arg.location,
+                arg.end_location,
+            ));
+        }
+    }
+
+    if returns.is_none() {
+        diagnostics.push(missing(format!("Function '{}' is missing a return type annotation", name), &stmt.location, None));
+    }
+}
+
+/// Checks the public functions in `body`, descending into class bodies for
+/// methods. Functions nested in functions aren't public API.
+fn check_body(body: &[ast::Stmt], in_class: bool, diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in body {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, args, returns, .. }
+            | ast::StmtKind::AsyncFunctionDef { name, args, returns, .. }
+                if !name.starts_with('_') =>
+            {
+                check_function(stmt, name, args, returns.as_deref(), in_class, diagnostics);
+            }
+            ast::StmtKind::ClassDef { name, body, .. } if !name.starts_with('_') => {
+                check_body(body, true, diagnostics);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl super::Rule for MissingTypeAnnotations {
+    fn code(&self) -> &'static str {
+        "ANN001"
+    }
+
+    fn name(&self) -> &'static str {
+        "missing-type-annotation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags public functions and methods with unannotated parameters or no return annotation."
+    }
+
+    fn default_enabled(&self) -> bool {
+        false
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        check_body(context.ast, false, &mut diagnostics);
+        Ok(diagnostics)
+    }
+}
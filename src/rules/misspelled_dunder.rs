@@ -0,0 +1,89 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+/// Special method names, without their surrounding underscores.
+const DUNDER_METHODS: &[&str] = &[
+    "abs", "add", "aenter", "aexit", "aiter", "and", "anext", "await", "bool", "buffer", "bytes",
+    "call", "ceil", "class_getitem", "complex", "contains", "copy", "deepcopy", "del", "delattr",
+    "delete", "delitem", "dir", "divmod", "enter", "eq", "exit", "float", "floor", "floordiv",
+    "format", "fspath", "ge", "get", "getattr", "getattribute", "getitem", "getnewargs",
+    "getnewargs_ex", "getstate", "gt", "hash", "iadd", "iand", "ifloordiv", "ilshift", "imatmul",
+    "imod", "imul", "index", "init", "init_subclass", "instancecheck", "int", "invert", "ior",
+    "ipow", "irshift", "isub", "iter", "itruediv", "ixor", "le", "len", "length_hint", "lshift",
+    "lt", "matmul", "missing", "mod", "mul", "ne", "neg", "new", "next", "or", "pos", "post_init",
+    "pow", "radd", "rand", "rdivmod", "reduce", "reduce_ex", "release_buffer", "repr", "reversed",
+    "rfloordiv", "rlshift", "rmatmul", "rmod", "rmul", "ror", "round", "rpow", "rrshift",
+    "rshift", "rsub", "rtruediv", "rxor", "set", "set_name", "setattr", "setitem", "setstate",
+    "sizeof", "str", "sub", "subclasscheck", "subclasshook", "truediv", "trunc", "xor",
+];
+
+pub struct MisspelledDunder;
+
+#[derive(Default)]
+struct MisspelledDunderVisitor {
+    in_class_body: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether `name` is a special method name with the wrong number of
+/// underscores, such as `__init_`, `_init__` or `__str`. A single leading
+/// underscore alone (`_str`) is an ordinary private method.
+fn is_misspelled_dunder(name: &str) -> bool {
+    let core = name.trim_matches('_');
+    let leading = name.len() - name.trim_start_matches('_').len();
+    let trailing = name.len() - name.trim_end_matches('_').len();
+    DUNDER_METHODS.contains(&core) && (leading, trailing) != (2, 2) && (leading >= 2 || trailing >= 2)
+}
+
+impl Visitor for MisspelledDunderVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { name, .. } | ast::StmtKind::AsyncFunctionDef { name, .. } => {
+                if self.in_class_body && is_misspelled_dunder(name) {
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: format!("Method '{}' looks like a misspelled dunder method", name),
+                        line: stmt.location.row(),
+                        column: stmt.location.column() + 1,
+                        end_line: stmt.location.row(),
+                        end_column: stmt.location.column() + 1,
+                        path: String::new(),
+                        code: "PLW3201".to_string(),
+                    });
+                }
+                let in_class_body = std::mem::replace(&mut self.in_class_body, false);
+                visitor::walk_stmt(self, stmt);
+                self.in_class_body = in_class_body;
+            }
+            ast::StmtKind::ClassDef { .. } => {
+                let in_class_body = std::mem::replace(&mut self.in_class_body, true);
+                visitor::walk_stmt(self, stmt);
+                self.in_class_body = in_class_body;
+            }
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+}
+
+impl super::Rule for MisspelledDunder {
+    fn code(&self) -> &'static str {
+        "PLW3201"
+    }
+
+    fn name(&self) -> &'static str {
+        "bad-dunder-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags methods named like a special method with missing or extra underscores, which silently don't override it."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = MisspelledDunderVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -1,23 +1,377 @@
 mod unused_imports;
 mod line_length;
 mod naming_conventions;
+mod none_comparison;
+mod star_import;
+mod fstring_placeholders;
+mod trailing_whitespace;
+mod complexity;
+mod builtin_shadowing;
+mod unused_variables;
+mod lambda_assignment;
+mod bool_comparison;
+mod blank_lines;
+mod duplicate_dict_key;
+mod file_encoding;
+mod final_newline;
+mod line_endings;
+mod assert_on_tuple;
+mod global_statement;
+mod multiple_imports;
+mod import_order;
+mod print_statement;
+mod redefinition;
+mod len_comparison;
+mod string_concat_in_loop;
+mod constant_comparison;
+mod first_arg_self;
+mod empty_body;
+mod missing_docstring;
+mod except_order;
+mod raise_without_from;
+mod function_length;
+mod misspelled_dunder;
+mod redundant_pass;
+mod inconsistent_returns;
+mod missing_type_annotations;
+mod attribute_outside_init;
+mod silent_except;
+mod is_literal;
+mod loop_variable_shadowing;
+mod fstring_nested_quotes;
+mod suspicious_assign_target;
+mod not_in_not_eq;
+mod open_without_with;
+mod duplicate_definition;
+mod incompatible_comparison;
+mod return_in_finally;
+mod ambiguous_name;
+mod implicit_namespace_package;
+mod trailing_comma;
+mod float_equality;
+mod unused_exception_variable;
+mod indentation;
+mod tab_indentation;
+mod trailing_blank_lines;
+pub(crate) mod visitor;
 
 use anyhow::Result;
 use rustpython_parser::ast;
-use crate::linter::Diagnostic;
+use crate::config::Config;
+use crate::fix::Edit;
+use crate::linter::{Diagnostic, LintContext};
 
 pub use unused_imports::UnusedImports;
 pub use line_length::LineLength;
 pub use naming_conventions::NamingConventions;
+pub use none_comparison::NoneComparison;
+pub use star_import::StarImport;
+pub use fstring_placeholders::FStringMissingPlaceholders;
+pub use trailing_whitespace::TrailingWhitespace;
+pub use complexity::Complexity;
+pub use builtin_shadowing::BuiltinShadowing;
+pub use unused_variables::UnusedVariables;
+pub use lambda_assignment::LambdaAssignment;
+pub use bool_comparison::BoolComparison;
+pub use blank_lines::{BlankLinesBeforeDef, BlankLinesBeforeMethod};
+pub use duplicate_dict_key::DuplicateDictKey;
+pub use file_encoding::FileEncoding;
+pub use final_newline::FinalNewline;
+pub use line_endings::LineEndings;
+pub use assert_on_tuple::AssertOnTuple;
+pub use global_statement::GlobalStatement;
+pub use multiple_imports::MultipleImports;
+pub use import_order::ImportOrder;
+pub use print_statement::PrintStatement;
+pub use redefinition::Redefinition;
+pub use len_comparison::LenComparison;
+pub use string_concat_in_loop::StringConcatInLoop;
+pub use constant_comparison::ConstantComparison;
+pub use first_arg_self::FirstArgSelf;
+pub use empty_body::EmptyBody;
+pub use missing_docstring::MissingDocstring;
+pub use except_order::ExceptOrder;
+pub use raise_without_from::RaiseWithoutFrom;
+pub use function_length::FunctionLength;
+pub use misspelled_dunder::MisspelledDunder;
+pub use redundant_pass::RedundantPass;
+pub use inconsistent_returns::InconsistentReturns;
+pub use missing_type_annotations::MissingTypeAnnotations;
+pub use attribute_outside_init::AttributeOutsideInit;
+pub use silent_except::SilentExcept;
+pub use is_literal::IsLiteral;
+pub use loop_variable_shadowing::LoopVariableShadowing;
+pub use fstring_nested_quotes::FStringNestedQuotes;
+pub use suspicious_assign_target::SuspiciousAssignTarget;
+pub use not_in_not_eq::NotInNotEq;
+pub use open_without_with::OpenWithoutWith;
+pub use duplicate_definition::DuplicateDefinition;
+pub use incompatible_comparison::IncompatibleComparison;
+pub use return_in_finally::ReturnInFinally;
+pub use ambiguous_name::AmbiguousName;
+pub use implicit_namespace_package::ImplicitNamespacePackage;
+pub use trailing_comma::TrailingComma;
+pub use float_equality::FloatEquality;
+pub use unused_exception_variable::UnusedExceptionVariable;
+pub use indentation::Indentation;
+pub use tab_indentation::TabIndentation;
+pub use trailing_blank_lines::TrailingBlankLines;
+pub(crate) use implicit_namespace_package::in_package;
 
 pub trait Rule: Send + Sync {
-    fn check(&self, ast: &ast::Suite, source: &str) -> Result<Vec<Diagnostic>>;
+    /// Stable code identifying the rule, e.g. `E501`.
+    fn code(&self) -> &'static str;
+
+    /// Short kebab-case name, e.g. `line-too-long`.
+    fn name(&self) -> &'static str;
+
+    /// One-sentence summary of what the rule checks.
+    fn description(&self) -> &'static str;
+
+    /// Diagnostics for the file described by `context`.
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>>;
+
+    /// Whether the rule runs when the config doesn't say otherwise. Opt-in
+    /// rules return `false` and are turned on through `[rules.enabled]`.
+    fn default_enabled(&self) -> bool {
+        true
+    }
+
+    /// Edits that resolve this rule's diagnostics, or `None` if the rule can't fix anything.
+    fn fix(&self, _context: &LintContext) -> Result<Option<Vec<Edit>>> {
+        Ok(None)
+    }
+}
+
+/// Source text of an expression that fits on one line.
+pub(crate) fn expr_source<'a>(source: &'a str, expr: &ast::Expr) -> Option<&'a str> {
+    let end = expr.end_location.filter(|end| end.row() == expr.location.row())?;
+    let line = source.lines().nth(expr.location.row().checked_sub(1)?)?;
+    // Columns count characters, so map them to byte offsets before slicing
+    let byte = |column: usize| line.char_indices().map(|(index, _)| index).chain([line.len()]).nth(column);
+    line.get(byte(expr.location.column())?..byte(end.column())?)
+}
+
+/// The name of a decorator such as `@staticmethod` or `@abc.abstractmethod`,
+/// ignoring any call arguments.
+pub(crate) fn decorator_name(decorator: &ast::Expr) -> Option<&str> {
+    match &decorator.node {
+        ast::ExprKind::Name { id, .. } => Some(id),
+        ast::ExprKind::Attribute { attr, .. } => Some(attr),
+        ast::ExprKind::Call { func, .. } => decorator_name(func),
+        _ => None,
+    }
+}
+
+/// Whether a body does nothing at all: a lone `pass` or `...`.
+pub(crate) fn is_empty_body(body: &[ast::Stmt]) -> bool {
+    match body {
+        [stmt] => match &stmt.node {
+            ast::StmtKind::Pass => true,
+            ast::StmtKind::Expr { value } => {
+                matches!(&value.node, ast::ExprKind::Constant { value: ast::Constant::Ellipsis, .. })
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Whether `decorator` is `@overload` (or `@typing.overload`), marking a
+/// type stub that a later definition of the same name implements.
+pub(crate) fn is_overload(decorator: &ast::Expr) -> bool {
+    decorator_name(decorator) == Some("overload")
+}
+
+/// The blocks directly inside a statement, including function and class bodies.
+pub(crate) fn nested_blocks(stmt: &ast::Stmt) -> Vec<&[ast::Stmt]> {
+    match &stmt.node {
+        ast::StmtKind::FunctionDef { body, .. }
+        | ast::StmtKind::AsyncFunctionDef { body, .. }
+        | ast::StmtKind::ClassDef { body, .. }
+        | ast::StmtKind::With { body, .. }
+        | ast::StmtKind::AsyncWith { body, .. } => vec![body],
+        ast::StmtKind::For { body, orelse, .. }
+        | ast::StmtKind::AsyncFor { body, orelse, .. }
+        | ast::StmtKind::While { body, orelse, .. }
+        | ast::StmtKind::If { body, orelse, .. } => vec![body, orelse],
+        ast::StmtKind::Try { body, handlers, orelse, finalbody } => {
+            let mut blocks: Vec<&[ast::Stmt]> = vec![body, orelse, finalbody];
+            for handler in handlers {
+                let ast::ExcepthandlerKind::ExceptHandler { body, .. } = &handler.node;
+                blocks.push(body);
+            }
+            blocks
+        }
+        ast::StmtKind::Match { cases, .. } => cases.iter().map(|case| case.body.as_slice()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Static information describing a rule, independent of its configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMetadata {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Metadata for every default rule, in registration order.
+pub fn list_rules() -> Vec<RuleMetadata> {
+    get_default_rules()
+        .iter()
+        .map(|rule| RuleMetadata {
+            code: rule.code(),
+            name: rule.name(),
+            description: rule.description(),
+        })
+        .collect()
 }
 
 pub fn get_default_rules() -> Vec<Box<dyn Rule>> {
-    vec![
-        Box::new(UnusedImports),
-        Box::new(LineLength::new(88)), // Default to 88 characters (black formatter default)
+    get_rules(&Config::default())
+}
+
+pub fn get_rules(config: &Config) -> Vec<Box<dyn Rule>> {
+    let mut rules: Vec<Box<dyn Rule>> = vec![
+        Box::new(UnusedImports::new(&config.rules.allow_unused_in)),
+        Box::new(LineLength::new(config.rules.max_line_length).with_ignore_long_strings(config.rules.ignore_long_strings)),
         Box::new(NamingConventions),
-    ]
+        Box::new(NoneComparison),
+        Box::new(StarImport),
+        Box::new(FStringMissingPlaceholders),
+        Box::new(TrailingWhitespace),
+        Box::new(Complexity::new(config.rules.max_complexity)),
+        Box::new(BuiltinShadowing),
+        Box::new(LambdaAssignment),
+        Box::new(BoolComparison),
+        Box::new(BlankLinesBeforeDef),
+        Box::new(BlankLinesBeforeMethod),
+        Box::new(DuplicateDictKey),
+        Box::new(FileEncoding),
+        Box::new(FinalNewline),
+        Box::new(TrailingBlankLines),
+        Box::new(LineEndings::new(config.rules.line_ending)),
+        Box::new(AssertOnTuple),
+        Box::new(MultipleImports),
+        Box::new(ImportOrder),
+        Box::new(Redefinition),
+        Box::new(LenComparison),
+        Box::new(StringConcatInLoop),
+        Box::new(ConstantComparison),
+        Box::new(FirstArgSelf),
+        Box::new(EmptyBody),
+        Box::new(ExceptOrder),
+        Box::new(RaiseWithoutFrom),
+        Box::new(FunctionLength::new(config.rules.max_function_lines)),
+        Box::new(MisspelledDunder),
+        Box::new(RedundantPass),
+        Box::new(InconsistentReturns),
+        Box::new(AttributeOutsideInit),
+        Box::new(SilentExcept),
+        Box::new(IsLiteral),
+        Box::new(LoopVariableShadowing),
+        Box::new(FStringNestedQuotes::new(config.target_version)),
+        Box::new(SuspiciousAssignTarget),
+        Box::new(NotInNotEq),
+        Box::new(OpenWithoutWith),
+        Box::new(DuplicateDefinition),
+        Box::new(IncompatibleComparison),
+        Box::new(ReturnInFinally),
+        Box::new(AmbiguousName::new(config.rules.ambiguous_names.clone())),
+        Box::new(ImplicitNamespacePackage),
+        Box::new(TrailingComma::new(config.rules.trailing_comma)),
+        Box::new(FloatEquality),
+        Box::new(UnusedExceptionVariable),
+        Box::new(Indentation::new(config.rules.indent_size, config.rules.use_tabs)),
+        Box::new(TabIndentation::new(config.rules.use_tabs)),
+        Box::new(UnusedVariables),
+        Box::new(GlobalStatement),
+        Box::new(PrintStatement),
+        Box::new(MissingDocstring),
+        Box::new(MissingTypeAnnotations),
+    ];
+
+    rules.retain(|rule| is_enabled(rule.as_ref(), config));
+    rules
+}
+
+/// Whether a built-in rule runs under `config`: an entry in `[rules.enabled]`
+/// wins, then the older per-rule switches, then the rule's own default.
+fn is_enabled(rule: &dyn Rule, config: &Config) -> bool {
+    if let Some(&enabled) = config.rules.enabled.get(rule.code()) {
+        return enabled;
+    }
+    match rule.code() {
+        "F841" => !config.rules.ignore_unused_variables,
+        "PLW0603" => config.rules.warn_global,
+        "T201" => config.rules.warn_print,
+        "D100" => config.rules.require_docstrings,
+        "ANN001" => config.rules.require_annotations,
+        _ => rule.default_enabled(),
+    }
+}
+
+/// The set of rules a run applies: the built-ins plus any custom rules a
+/// downstream crate registers. Derefs to a slice of rules, so it can be passed
+/// wherever a rule list is expected.
+#[derive(Default)]
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn Rule + Sync>>,
+}
+
+impl RuleRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in rules with the default configuration.
+    pub fn with_defaults() -> Self {
+        Self::from_config(&Config::default())
+    }
+
+    /// The built-in rules as configured by `config`.
+    pub fn from_config(config: &Config) -> Self {
+        get_rules(config).into_iter().map(|r| r as Box<dyn Rule + Sync>).collect()
+    }
+
+    /// Adds a rule; it runs after the rules already registered.
+    pub fn register(&mut self, rule: Box<dyn Rule + Sync>) {
+        self.rules.push(rule);
+    }
+
+    /// Keeps only the rules selected and not ignored, as in [`filter_rules`].
+    pub fn filter(mut self, select: &[String], ignore: &[String]) -> Self {
+        self.rules.retain(|rule| is_selected(rule.code(), select, ignore));
+        self
+    }
+}
+
+impl std::ops::Deref for RuleRegistry {
+    type Target = [Box<dyn Rule + Sync>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.rules
+    }
+}
+
+impl FromIterator<Box<dyn Rule + Sync>> for RuleRegistry {
+    fn from_iter<I: IntoIterator<Item = Box<dyn Rule + Sync>>>(iter: I) -> Self {
+        Self { rules: iter.into_iter().collect() }
+    }
+}
+
+/// Keeps the rules whose code is in `select` (or all of them when `select` is
+/// empty) and drops any whose code is in `ignore`. `ignore` wins on conflict.
+pub fn filter_rules(rules: Vec<Box<dyn Rule>>, select: &[String], ignore: &[String]) -> Vec<Box<dyn Rule>> {
+    rules
+        .into_iter()
+        .filter(|rule| is_selected(rule.code(), select, ignore))
+        .collect()
+}
+
+fn is_selected(code: &str, select: &[String], ignore: &[String]) -> bool {
+    (select.is_empty() || select.iter().any(|selected| selected == code))
+        && !ignore.iter().any(|ignored| ignored == code)
 }
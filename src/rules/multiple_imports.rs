@@ -0,0 +1,53 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct MultipleImports;
+
+struct MultipleImportsVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for MultipleImportsVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        // `from x import a, b` is fine by PEP 8; only plain `import a, b` should be split
+        if let ast::StmtKind::Import { names } = &stmt.node {
+            if names.len() > 1 {
+                let end = stmt.end_location.unwrap_or(stmt.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "Multiple imports on one line; put each on its own line".to_string(),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column(),
+                    path: String::new(),
+                    code: "E401".to_string(),
+                });
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for MultipleImports {
+    fn code(&self) -> &'static str {
+        "E401"
+    }
+
+    fn name(&self) -> &'static str {
+        "multiple-imports-on-one-line"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'import' statements that import more than one module."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = MultipleImportsVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
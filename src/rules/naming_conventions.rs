@@ -1,6 +1,6 @@
 use anyhow::Result;
 use rustpython_parser::ast;
-use crate::linter::Diagnostic;
+use crate::linter::{Diagnostic, LintContext};
 use crate::linter::DiagnosticLevel;
 
 pub struct NamingConventions;
@@ -56,51 +56,79 @@ impl NamingConventions {
         Self::is_snake_case(name) || Self::is_constant(name)
     }
 
-    fn check_statements(&self, stmts: &[ast::Stmt], diagnostics: &mut Vec<Diagnostic>) {
+    /// 1-based column where a def or class statement's name starts, found by
+    /// skipping the `def`/`class` keyword on its first line.
+    fn name_column(lines: &[&str], stmt: &ast::Stmt) -> usize {
+        let column = stmt.location.column();
+        let Some(line) = lines.get(stmt.location.row() - 1) else {
+            return column + 1;
+        };
+        let Some(mut rest) = line.get(column..) else {
+            return column + 1;
+        };
+        let keyword = if matches!(stmt.node, ast::StmtKind::ClassDef { .. }) { "class" } else { "def" };
+        if let Some(after) = rest.strip_prefix(keyword) {
+            rest = after.trim_start();
+        }
+        line[..line.len() - rest.len()].chars().count() + 1
+    }
+
+    fn check_statements(&self, stmts: &[ast::Stmt], lines: &[&str], diagnostics: &mut Vec<Diagnostic>) {
         for stmt in stmts {
             match &stmt.node {
                 ast::StmtKind::FunctionDef { name, body, .. } => {
                     if !Self::is_snake_case(name) {
-                        println!("Found bad function name: {}", name);
+                        log::debug!("Found bad function name: {}", name);
+                        let column = Self::name_column(lines, stmt);
                         diagnostics.push(Diagnostic {
                             level: DiagnosticLevel::Warning,
                             message: format!("Function '{}' should use snake_case", name),
                             line: stmt.location.row(),
-                            column: 1,
+                            column,
+                            end_line: stmt.location.row(),
+                            end_column: column + name.chars().count() - 1,
                             path: String::new(),
+                            code: "N802".to_string(),
                         });
                     }
                     // Recurse into function body
-                    self.check_statements(body, diagnostics);
+                    self.check_statements(body, lines, diagnostics);
                 }
                 ast::StmtKind::ClassDef { name, body, .. } => {
                     if !Self::is_pascal_case(name) {
-                        println!("Found bad class name: {}", name);
+                        log::debug!("Found bad class name: {}", name);
+                        let column = Self::name_column(lines, stmt);
                         diagnostics.push(Diagnostic {
                             level: DiagnosticLevel::Warning,
                             message: format!("Class '{}' should use PascalCase", name),
                             line: stmt.location.row(),
-                            column: 1,
+                            column,
+                            end_line: stmt.location.row(),
+                            end_column: column + name.chars().count() - 1,
                             path: String::new(),
+                            code: "N802".to_string(),
                         });
                     }
                     // Recurse into class body
-                    self.check_statements(body, diagnostics);
+                    self.check_statements(body, lines, diagnostics);
                 }
                 ast::StmtKind::Assign { targets, value, .. } => {
                     for target in targets {
                         if let ast::ExprKind::Name { id, .. } = &target.node {
                             if !Self::is_valid_variable_name(id) {
-                                println!("Found bad variable name: {}", id);
+                                log::debug!("Found bad variable name: {}", id);
                                 diagnostics.push(Diagnostic {
                                     level: DiagnosticLevel::Warning,
                                     message: format!(
                                         "Variable '{}' should use snake_case or be a proper constant",
                                         id
                                     ),
-                                    line: stmt.location.row(),
-                                    column: 1,
+                                    line: target.location.row(),
+                                    column: target.location.column() + 1,
+                                    end_line: target.location.row(),
+                                    end_column: target.location.column() + id.chars().count(),
                                     path: String::new(),
+                                    code: "N802".to_string(),
                                 });
                             }
                         }
@@ -110,19 +138,19 @@ impl NamingConventions {
                 }
                 ast::StmtKind::If { test, body, orelse, .. } => {
                     self.check_expr(test, diagnostics);
-                    self.check_statements(body, diagnostics);
-                    self.check_statements(orelse, diagnostics);
+                    self.check_statements(body, lines, diagnostics);
+                    self.check_statements(orelse, lines, diagnostics);
                 }
                 ast::StmtKind::While { test, body, orelse, .. } => {
                     self.check_expr(test, diagnostics);
-                    self.check_statements(body, diagnostics);
-                    self.check_statements(orelse, diagnostics);
+                    self.check_statements(body, lines, diagnostics);
+                    self.check_statements(orelse, lines, diagnostics);
                 }
                 ast::StmtKind::For { target, iter, body, orelse, .. } => {
                     self.check_expr(target, diagnostics);
                     self.check_expr(iter, diagnostics);
-                    self.check_statements(body, diagnostics);
-                    self.check_statements(orelse, diagnostics);
+                    self.check_statements(body, lines, diagnostics);
+                    self.check_statements(orelse, lines, diagnostics);
                 }
                 ast::StmtKind::Expr { value, .. } => {
                     self.check_expr(value, diagnostics);
@@ -132,6 +160,7 @@ impl NamingConventions {
         }
     }
 
+    #[allow(clippy::only_used_in_recursion)]
     fn check_expr(&self, expr: &ast::Expr, diagnostics: &mut Vec<Diagnostic>) {
         match &expr.node {
             ast::ExprKind::Lambda { body, .. } => {
@@ -167,10 +196,22 @@ impl NamingConventions {
 }
 
 impl super::Rule for NamingConventions {
-    fn check(&self, ast: &ast::Suite, _source: &str) -> Result<Vec<Diagnostic>> {
+    fn code(&self) -> &'static str {
+        "N802"
+    }
+
+    fn name(&self) -> &'static str {
+        "invalid-name"
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks that functions use snake_case, classes use PascalCase, and variables use snake_case or UPPER_CASE."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
         let mut diagnostics = Vec::new();
-        self.check_statements(ast, &mut diagnostics);
-        println!("Total diagnostics: {}", diagnostics.len());
+        self.check_statements(context.ast, &context.lines, &mut diagnostics);
+        log::trace!("Total diagnostics: {}", diagnostics.len());
         Ok(diagnostics)
     }
 }
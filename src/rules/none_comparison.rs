@@ -0,0 +1,69 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct NoneComparison;
+
+struct NoneComparisonVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_none(expr: &ast::Expr) -> bool {
+    matches!(
+        &expr.node,
+        ast::ExprKind::Constant { value: ast::Constant::None, .. }
+    )
+}
+
+impl Visitor for NoneComparisonVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Compare { left, ops, comparators } = &expr.node {
+            // In a chain like `a == None == b`, ops[i] sits between operands[i] and operands[i + 1]
+            let operands: Vec<&ast::Expr> = std::iter::once(left.as_ref()).chain(comparators).collect();
+            for (i, op) in ops.iter().enumerate() {
+                let (lhs, rhs) = (operands[i], operands[i + 1]);
+                if !is_none(lhs) && !is_none(rhs) {
+                    continue;
+                }
+                let message = match op {
+                    ast::Cmpop::Eq => "Use 'is None' instead of '== None'",
+                    ast::Cmpop::NotEq => "Use 'is not None' instead of '!= None'",
+                    _ => continue,
+                };
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: message.to_string(),
+                    line: rhs.location.row(),
+                    column: rhs.location.column() + 1,
+                    end_line: rhs.location.row(),
+                    end_column: rhs.location.column() + 1,
+                    path: String::new(),
+                    code: "E711".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for NoneComparison {
+    fn code(&self) -> &'static str {
+        "E711"
+    }
+
+    fn name(&self) -> &'static str {
+        "none-comparison"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags comparisons to None using '==' or '!=' instead of 'is' or 'is not'."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = NoneComparisonVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct NotInNotEq;
+
+struct NotInNotEqVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for NotInNotEqVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::UnaryOp { op: ast::Unaryop::Not, operand } = &expr.node {
+            // Only a single comparison has a direct negated form; `not a == b == c` doesn't
+            if let ast::ExprKind::Compare { ops, .. } = &operand.node {
+                let message = match ops.as_slice() {
+                    [ast::Cmpop::Eq] => Some("Use 'a != b' instead of 'not a == b'"),
+                    [ast::Cmpop::In] => Some("Use 'a not in b' instead of 'not a in b'"),
+                    _ => None,
+                };
+                if let Some(message) = message {
+                    let end = expr.end_location.filter(|end| end.row() == expr.location.row()).unwrap_or(expr.location);
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: message.to_string(),
+                        line: expr.location.row(),
+                        column: expr.location.column() + 1,
+                        end_line: end.row(),
+                        end_column: end.column().max(expr.location.column() + 1),
+                        path: String::new(),
+                        code: "E713".to_string(),
+                    });
+                }
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for NotInNotEq {
+    fn code(&self) -> &'static str {
+        "E713"
+    }
+
+    fn name(&self) -> &'static str {
+        "not-in-not-eq"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'not a == b' and 'not a in b', which read better as 'a != b' and 'a not in b'."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = NotInNotEqVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
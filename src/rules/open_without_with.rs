@@ -0,0 +1,63 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct OpenWithoutWith;
+
+struct OpenWithoutWithVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_open_call(expr: &ast::Expr) -> bool {
+    match &expr.node {
+        ast::ExprKind::Call { func, .. } => matches!(&func.node, ast::ExprKind::Name { id, .. } if id == "open"),
+        _ => false,
+    }
+}
+
+impl Visitor for OpenWithoutWithVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        // `with open(...) as f:` binds through the with item, not an assignment
+        let value = match &stmt.node {
+            ast::StmtKind::Assign { value, .. } => Some(value.as_ref()),
+            ast::StmtKind::AnnAssign { value: Some(value), .. } => Some(value.as_ref()),
+            _ => None,
+        };
+        if let Some(call) = value.filter(|value| is_open_call(value)) {
+            let end = call.end_location.filter(|end| end.row() == call.location.row()).unwrap_or(call.location);
+            self.diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "File opened without a context manager; use 'with open(...)'".to_string(),
+                line: call.location.row(),
+                column: call.location.column() + 1,
+                end_line: end.row(),
+                end_column: end.column().max(call.location.column() + 1),
+                path: String::new(),
+                code: "SIM115".to_string(),
+            });
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for OpenWithoutWith {
+    fn code(&self) -> &'static str {
+        "SIM115"
+    }
+
+    fn name(&self) -> &'static str {
+        "open-without-with"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'open()' results assigned to a variable instead of being used in a 'with' statement."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = OpenWithoutWithVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
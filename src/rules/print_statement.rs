@@ -0,0 +1,57 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct PrintStatement;
+
+struct PrintStatementVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for PrintStatementVisitor {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Call { func, .. } = &expr.node {
+            // Only the builtin; `logger.print(...)` is an attribute call and is left alone
+            if matches!(&func.node, ast::ExprKind::Name { id, .. } if id == "print") {
+                let end = func.end_location.unwrap_or(func.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "'print' found; remove it or use logging".to_string(),
+                    line: func.location.row(),
+                    column: func.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column(),
+                    path: String::new(),
+                    code: "T201".to_string(),
+                });
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for PrintStatement {
+    fn code(&self) -> &'static str {
+        "T201"
+    }
+
+    fn name(&self) -> &'static str {
+        "print"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags calls to the builtin print, which are often debugging leftovers."
+    }
+
+    fn default_enabled(&self) -> bool {
+        false
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = PrintStatementVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
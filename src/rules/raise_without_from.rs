@@ -0,0 +1,77 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct RaiseWithoutFrom;
+
+#[derive(Default)]
+struct RaiseWithoutFromVisitor {
+    /// The `as` name of each enclosing except handler, innermost last.
+    handlers: Vec<Option<String>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for RaiseWithoutFromVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            // A function defined in a handler runs later, outside of it
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } | ast::StmtKind::ClassDef { .. } => {
+                let handlers = std::mem::take(&mut self.handlers);
+                visitor::walk_stmt(self, stmt);
+                self.handlers = handlers;
+                return;
+            }
+            ast::StmtKind::Raise { exc: Some(exc), cause: None } if !self.handlers.is_empty() => {
+                // `raise err` re-raises the caught exception with its context intact
+                let reraise = matches!(
+                    &exc.node,
+                    ast::ExprKind::Name { id, .. } if self.handlers.iter().any(|name| name.as_deref() == Some(id.as_str()))
+                );
+                if !reraise {
+                    let end = stmt.end_location.unwrap_or(stmt.location);
+                    self.diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: "Consider 'raise ... from err' to preserve the exception chain".to_string(),
+                        line: stmt.location.row(),
+                        column: stmt.location.column() + 1,
+                        end_line: end.row(),
+                        end_column: end.column().max(stmt.location.column() + 1),
+                        path: String::new(),
+                        code: "B904".to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_excepthandler(&mut self, handler: &ast::Excepthandler) {
+        let ast::ExcepthandlerKind::ExceptHandler { name, .. } = &handler.node;
+        self.handlers.push(name.clone());
+        visitor::walk_excepthandler(self, handler);
+        self.handlers.pop();
+    }
+}
+
+impl super::Rule for RaiseWithoutFrom {
+    fn code(&self) -> &'static str {
+        "B904"
+    }
+
+    fn name(&self) -> &'static str {
+        "raise-without-from-inside-except"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags raising a new exception inside an except handler without 'from', which hides the original cause."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = RaiseWithoutFromVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
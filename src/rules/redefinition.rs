@@ -0,0 +1,166 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+use super::{is_overload, nested_blocks};
+use std::collections::{HashMap, HashSet};
+
+pub struct Redefinition;
+
+/// Records every name a statement reads or binds anywhere inside it,
+/// including in nested blocks and closures.
+#[derive(Default)]
+struct TouchedNames {
+    read: HashSet<String>,
+    bound: HashSet<String>,
+}
+
+impl Visitor for TouchedNames {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        self.bound.extend(bindings(stmt).into_iter().map(|(name, _)| name));
+        // `x += 1` reads x even though its target is a store
+        if let ast::StmtKind::AugAssign { target, .. } = &stmt.node {
+            if let ast::ExprKind::Name { id, .. } = &target.node {
+                self.read.insert(id.to_string());
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Name { id, ctx } = &expr.node {
+            match ctx {
+                ast::ExprContext::Store => self.bound.insert(id.to_string()),
+                _ => self.read.insert(id.to_string()),
+            };
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+/// Names a simple statement binds directly, with the location of each binding.
+/// Dotted imports without an alias are skipped since `import a.b` and
+/// `import a.c` both bind `a` on purpose.
+fn bindings(stmt: &ast::Stmt) -> Vec<(String, ast::Location)> {
+    match &stmt.node {
+        ast::StmtKind::Import { names } | ast::StmtKind::ImportFrom { names, .. } => names
+            .iter()
+            .filter(|alias| alias.node.name != "*")
+            .filter_map(|alias| match &alias.node.asname {
+                Some(asname) => Some((asname.to_string(), alias.location)),
+                None if alias.node.name.contains('.') => None,
+                None => Some((alias.node.name.to_string(), alias.location)),
+            })
+            .collect(),
+        ast::StmtKind::Assign { targets, .. } => targets
+            .iter()
+            .filter_map(|target| match &target.node {
+                ast::ExprKind::Name { id, .. } => Some((id.to_string(), target.location)),
+                _ => None,
+            })
+            .collect(),
+        ast::StmtKind::AnnAssign { target, value: Some(_), .. } => match &target.node {
+            ast::ExprKind::Name { id, .. } => vec![(id.to_string(), target.location)],
+            _ => Vec::new(),
+        },
+        ast::StmtKind::FunctionDef { name, decorator_list, .. }
+        | ast::StmtKind::AsyncFunctionDef { name, decorator_list, .. }
+            if !decorator_list.iter().any(is_overload) =>
+        {
+            vec![(name.to_string(), stmt.location)]
+        }
+        ast::StmtKind::ClassDef { name, .. } => vec![(name.to_string(), stmt.location)],
+        _ => Vec::new(),
+    }
+}
+
+fn is_simple(stmt: &ast::Stmt) -> bool {
+    matches!(
+        stmt.node,
+        ast::StmtKind::Import { .. }
+            | ast::StmtKind::ImportFrom { .. }
+            | ast::StmtKind::Assign { .. }
+            | ast::StmtKind::AnnAssign { .. }
+            | ast::StmtKind::FunctionDef { .. }
+            | ast::StmtKind::AsyncFunctionDef { .. }
+            | ast::StmtKind::ClassDef { .. }
+    )
+}
+
+fn is_definition(stmt: &ast::Stmt) -> bool {
+    matches!(
+        stmt.node,
+        ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } | ast::StmtKind::ClassDef { .. }
+    )
+}
+
+/// Checks one straight-line block. Bindings inside branches and loops may or
+/// may not run, so those only ever clear pending names and are checked as
+/// blocks of their own.
+fn check_block(block: &[ast::Stmt], diagnostics: &mut Vec<Diagnostic>) {
+    // Names bound in this block and not read since, with the line they were
+    // bound on and whether that binding was a function or class definition
+    let mut pending: HashMap<String, (usize, bool)> = HashMap::new();
+
+    for stmt in block {
+        let mut touched = TouchedNames::default();
+        touched.visit_stmt(stmt);
+        for name in &touched.read {
+            pending.remove(name);
+        }
+
+        if is_simple(stmt) {
+            let definition = is_definition(stmt);
+            for (name, location) in bindings(stmt) {
+                if name == "_" {
+                    continue;
+                }
+                if let Some((previous, previous_definition)) = pending.insert(name.clone(), (location.row(), definition)) {
+                    // A function or class defined twice is reported by E0102
+                    if definition && previous_definition {
+                        continue;
+                    }
+                    diagnostics.push(Diagnostic {
+                        level: DiagnosticLevel::Warning,
+                        message: format!("Redefinition of unused '{}' from line {}", name, previous),
+                        line: location.row(),
+                        column: location.column() + 1,
+                        end_line: location.row(),
+                        end_column: location.column() + name.chars().count(),
+                        path: String::new(),
+                        code: "F811".to_string(),
+                    });
+                }
+            }
+        } else {
+            for name in &touched.bound {
+                pending.remove(name);
+            }
+        }
+
+        for nested in nested_blocks(stmt) {
+            check_block(nested, diagnostics);
+        }
+    }
+}
+
+impl super::Rule for Redefinition {
+    fn code(&self) -> &'static str {
+        "F811"
+    }
+
+    fn name(&self) -> &'static str {
+        "redefined-while-unused"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags names that are imported or assigned again before the previous value was ever read."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        check_block(context.ast, &mut diagnostics);
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct RedundantPass;
+
+#[derive(Default)]
+struct RedundantPassVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl RedundantPassVisitor {
+    /// Flags every `pass` in a block that has other statements; a lone `pass`
+    /// is what makes an empty block valid.
+    fn check_suite(&mut self, suite: &[ast::Stmt]) {
+        if suite.len() < 2 {
+            return;
+        }
+        for stmt in suite.iter().filter(|stmt| matches!(stmt.node, ast::StmtKind::Pass)) {
+            self.diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "Redundant 'pass' statement".to_string(),
+                line: stmt.location.row(),
+                column: stmt.location.column() + 1,
+                end_line: stmt.location.row(),
+                end_column: stmt.location.column() + 4,
+                path: String::new(),
+                code: "PIE790".to_string(),
+            });
+        }
+    }
+}
+
+impl Visitor for RedundantPassVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { body, .. }
+            | ast::StmtKind::AsyncFunctionDef { body, .. }
+            | ast::StmtKind::ClassDef { body, .. }
+            | ast::StmtKind::With { body, .. }
+            | ast::StmtKind::AsyncWith { body, .. } => self.check_suite(body),
+            ast::StmtKind::For { body, orelse, .. }
+            | ast::StmtKind::AsyncFor { body, orelse, .. }
+            | ast::StmtKind::While { body, orelse, .. }
+            | ast::StmtKind::If { body, orelse, .. } => {
+                self.check_suite(body);
+                self.check_suite(orelse);
+            }
+            ast::StmtKind::Try { body, orelse, finalbody, .. } => {
+                self.check_suite(body);
+                self.check_suite(orelse);
+                self.check_suite(finalbody);
+            }
+            ast::StmtKind::Match { cases, .. } => {
+                for case in cases {
+                    self.check_suite(&case.body);
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_excepthandler(&mut self, handler: &ast::Excepthandler) {
+        let ast::ExcepthandlerKind::ExceptHandler { body, .. } = &handler.node;
+        self.check_suite(body);
+        visitor::walk_excepthandler(self, handler);
+    }
+}
+
+impl super::Rule for RedundantPass {
+    fn code(&self) -> &'static str {
+        "PIE790"
+    }
+
+    fn name(&self) -> &'static str {
+        "unnecessary-pass"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'pass' statements in blocks that already contain other statements."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = RedundantPassVisitor::default();
+        visitor.check_suite(context.ast);
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
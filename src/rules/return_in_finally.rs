@@ -0,0 +1,102 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct ReturnInFinally;
+
+#[derive(Default)]
+struct ReturnInFinallyVisitor {
+    /// Whether the statements being walked run as part of a `finally` block.
+    in_finally: bool,
+    /// Loops entered since the innermost `finally`; `break` and `continue`
+    /// inside them stay in the block.
+    loops: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl ReturnInFinallyVisitor {
+    /// Walks `suite` with `in_finally` and `loops` replaced, restoring them afterwards.
+    fn walk_with(&mut self, suite: &[ast::Stmt], in_finally: bool, loops: usize) {
+        let saved = (self.in_finally, self.loops);
+        (self.in_finally, self.loops) = (in_finally, loops);
+        visitor::walk_suite(self, suite);
+        (self.in_finally, self.loops) = saved;
+    }
+
+    fn report(&mut self, stmt: &ast::Stmt, keyword: &str) {
+        let end = stmt.end_location.filter(|end| end.row() == stmt.location.row()).unwrap_or(stmt.location);
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: format!("'{}' in a 'finally' block swallows exceptions", keyword),
+            line: stmt.location.row(),
+            column: stmt.location.column() + 1,
+            end_line: end.row(),
+            end_column: end.column().max(stmt.location.column() + 1),
+            path: String::new(),
+            code: "B012".to_string(),
+        });
+    }
+}
+
+impl Visitor for ReturnInFinallyVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            // A nested function returns from itself, not from the finally block
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } | ast::StmtKind::ClassDef { .. } => {
+                let saved = (self.in_finally, self.loops);
+                (self.in_finally, self.loops) = (false, 0);
+                visitor::walk_stmt(self, stmt);
+                (self.in_finally, self.loops) = saved;
+            }
+            ast::StmtKind::Try { body, handlers, orelse, finalbody } => {
+                visitor::walk_suite(self, body);
+                for handler in handlers {
+                    self.visit_excepthandler(handler);
+                }
+                visitor::walk_suite(self, orelse);
+                self.walk_with(finalbody, true, 0);
+            }
+            ast::StmtKind::For { target, iter, body, orelse, .. } | ast::StmtKind::AsyncFor { target, iter, body, orelse, .. } => {
+                self.visit_expr(target);
+                self.visit_expr(iter);
+                self.walk_with(body, self.in_finally, self.loops + 1);
+                // `break` in a loop's else clause leaves the enclosing loop
+                visitor::walk_suite(self, orelse);
+            }
+            ast::StmtKind::While { test, body, orelse } => {
+                self.visit_expr(test);
+                self.walk_with(body, self.in_finally, self.loops + 1);
+                visitor::walk_suite(self, orelse);
+            }
+            ast::StmtKind::Return { .. } if self.in_finally => {
+                self.report(stmt, "return");
+                visitor::walk_stmt(self, stmt);
+            }
+            ast::StmtKind::Break if self.in_finally && self.loops == 0 => self.report(stmt, "break"),
+            ast::StmtKind::Continue if self.in_finally && self.loops == 0 => self.report(stmt, "continue"),
+            _ => visitor::walk_stmt(self, stmt),
+        }
+    }
+}
+
+impl super::Rule for ReturnInFinally {
+    fn code(&self) -> &'static str {
+        "B012"
+    }
+
+    fn name(&self) -> &'static str {
+        "jump-statement-in-finally"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'return', 'break' and 'continue' that leave a 'finally' block, discarding any exception being raised."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = ReturnInFinallyVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct SilentExcept;
+
+/// Exceptions that are routinely caught just to exit quietly.
+const EXEMPT_EXCEPTIONS: &[&str] = &["KeyboardInterrupt", "SystemExit"];
+
+#[derive(Default)]
+struct SilentExceptVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether every exception a handler catches is one of [`EXEMPT_EXCEPTIONS`].
+fn is_exempt(type_: &ast::Expr) -> bool {
+    let exempt = |expr: &ast::Expr| {
+        let name = match &expr.node {
+            ast::ExprKind::Name { id, .. } => id,
+            ast::ExprKind::Attribute { attr, .. } => attr,
+            _ => return false,
+        };
+        EXEMPT_EXCEPTIONS.contains(&name.as_str())
+    };
+    match &type_.node {
+        ast::ExprKind::Tuple { elts, .. } => !elts.is_empty() && elts.iter().all(exempt),
+        _ => exempt(type_),
+    }
+}
+
+impl Visitor for SilentExceptVisitor {
+    fn visit_excepthandler(&mut self, handler: &ast::Excepthandler) {
+        let ast::ExcepthandlerKind::ExceptHandler { type_, body, .. } = &handler.node;
+        if super::is_empty_body(body) && !type_.as_deref().is_some_and(is_exempt) {
+            self.diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "Exception silently ignored; log or handle it".to_string(),
+                line: handler.location.row(),
+                column: handler.location.column() + 1,
+                end_line: handler.location.row(),
+                end_column: handler.location.column() + 6,
+                path: String::new(),
+                code: "S110".to_string(),
+            });
+        }
+        visitor::walk_excepthandler(self, handler);
+    }
+}
+
+impl super::Rule for SilentExcept {
+    fn code(&self) -> &'static str {
+        "S110"
+    }
+
+    fn name(&self) -> &'static str {
+        "try-except-pass"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags exception handlers that swallow the exception with only `pass` or `...`."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = SilentExceptVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
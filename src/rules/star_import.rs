@@ -0,0 +1,53 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct StarImport;
+
+impl super::Rule for StarImport {
+    fn code(&self) -> &'static str {
+        "F403"
+    }
+
+    fn name(&self) -> &'static str {
+        "star-import"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags wildcard 'from module import *' imports."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+
+        // Star imports are only allowed at module level, so the top-level suite is enough
+        for stmt in context.ast.iter() {
+            if let ast::StmtKind::ImportFrom { module, names, level } = &stmt.node {
+                if !names.iter().any(|alias| alias.node.name == "*") {
+                    continue;
+                }
+                let module_name = format!(
+                    "{}{}",
+                    ".".repeat(level.unwrap_or(0)),
+                    module.as_deref().unwrap_or("")
+                );
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!(
+                        "from '{}' import * used; import only the names you need",
+                        module_name
+                    ),
+                    line: stmt.location.row(),
+                    column: stmt.location.column() + 1,
+                    end_line: stmt.location.row(),
+                    end_column: stmt.location.column() + 1,
+                    path: String::new(),
+                    code: "F403".to_string(),
+                });
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use std::collections::HashSet;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct StringConcatInLoop;
+
+#[derive(Default)]
+struct StringConcatInLoopVisitor {
+    /// Names in the current scope last bound to a string, innermost scope last.
+    scopes: Vec<HashSet<String>>,
+    loop_depth: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_string(expr: &ast::Expr) -> bool {
+    matches!(
+        &expr.node,
+        ast::ExprKind::Constant { value: ast::Constant::Str(_), .. } | ast::ExprKind::JoinedStr { .. }
+    )
+}
+
+fn is_str_annotation(expr: &ast::Expr) -> bool {
+    matches!(&expr.node, ast::ExprKind::Name { id, .. } if id == "str")
+}
+
+fn name_of(expr: &ast::Expr) -> Option<&str> {
+    match &expr.node {
+        ast::ExprKind::Name { id, .. } => Some(id),
+        _ => None,
+    }
+}
+
+impl StringConcatInLoopVisitor {
+    fn strings(&mut self) -> &mut HashSet<String> {
+        self.scopes.last_mut().expect("module scope is always present")
+    }
+
+    fn is_accumulator(&self, name: &str) -> bool {
+        self.scopes.last().is_some_and(|strings| strings.contains(name))
+    }
+
+    /// Records whether `name` now holds a string, judging by the value bound to it.
+    fn bind(&mut self, name: &str, is_string: bool) {
+        if is_string {
+            self.strings().insert(name.to_string());
+        } else if self.loop_depth == 0 {
+            // Reassignments inside the loop are what we're looking for, so only
+            // forget the type of names rebound outside one
+            self.strings().remove(name);
+        }
+    }
+
+    fn report(&mut self, stmt: &ast::Stmt) {
+        let end = stmt.end_location.unwrap_or(stmt.location);
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: "String concatenation in a loop; consider ''.join()".to_string(),
+            line: stmt.location.row(),
+            column: stmt.location.column() + 1,
+            end_line: end.row(),
+            end_column: end.column().max(stmt.location.column() + 1),
+            path: String::new(),
+            code: "R1713".to_string(),
+        });
+    }
+
+    fn walk_loop(&mut self, body: &[ast::Stmt], orelse: &[ast::Stmt]) {
+        self.loop_depth += 1;
+        visitor::walk_suite(self, body);
+        self.loop_depth -= 1;
+        // The `else` clause runs once, after the loop
+        visitor::walk_suite(self, orelse);
+    }
+}
+
+impl Visitor for StringConcatInLoopVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { .. } | ast::StmtKind::AsyncFunctionDef { .. } | ast::StmtKind::ClassDef { .. } => {
+                // A nested scope has its own names and isn't inside the enclosing loop
+                let loop_depth = std::mem::take(&mut self.loop_depth);
+                self.scopes.push(HashSet::new());
+                visitor::walk_stmt(self, stmt);
+                self.scopes.pop();
+                self.loop_depth = loop_depth;
+                return;
+            }
+            ast::StmtKind::For { body, orelse, .. }
+            | ast::StmtKind::AsyncFor { body, orelse, .. }
+            | ast::StmtKind::While { body, orelse, .. } => {
+                self.walk_loop(body, orelse);
+                return;
+            }
+            ast::StmtKind::AugAssign { target, op: ast::Operator::Add, value } => {
+                if let Some(name) = name_of(target) {
+                    if self.loop_depth > 0 && self.is_accumulator(name) {
+                        self.report(stmt);
+                    } else if self.loop_depth == 0 && is_string(value) {
+                        self.bind(name, true);
+                    }
+                }
+            }
+            ast::StmtKind::AnnAssign { target, annotation, value, .. } => {
+                if let Some(name) = name_of(target) {
+                    let is_string = is_str_annotation(annotation) || value.as_deref().is_some_and(is_string);
+                    self.bind(name, is_string);
+                }
+            }
+            ast::StmtKind::Assign { targets, value, .. } => {
+                if let [target] = targets.as_slice() {
+                    if let Some(name) = name_of(target) {
+                        // `s = s + other`
+                        let concatenates = matches!(
+                            &value.node,
+                            ast::ExprKind::BinOp { left, op: ast::Operator::Add, .. } if name_of(left) == Some(name)
+                        );
+                        if concatenates && self.loop_depth > 0 && self.is_accumulator(name) {
+                            self.report(stmt);
+                        } else {
+                            self.bind(name, is_string(value));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for StringConcatInLoop {
+    fn code(&self) -> &'static str {
+        "R1713"
+    }
+
+    fn name(&self) -> &'static str {
+        "consider-using-join"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags building a string with '+=' inside a loop, which is quadratic; collect the parts and use ''.join()."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = StringConcatInLoopVisitor { scopes: vec![HashSet::new()], ..Default::default() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct SuspiciousAssignTarget;
+
+#[derive(Default)]
+struct SuspiciousAssignTargetVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether `expr` builds a fresh container that nothing else refers to.
+fn is_temporary(expr: &ast::Expr) -> bool {
+    matches!(
+        &expr.node,
+        ast::ExprKind::Dict { .. }
+            | ast::ExprKind::List { .. }
+            | ast::ExprKind::Tuple { .. }
+            | ast::ExprKind::Set { .. }
+            | ast::ExprKind::DictComp { .. }
+            | ast::ExprKind::ListComp { .. }
+            | ast::ExprKind::SetComp { .. }
+    )
+}
+
+impl SuspiciousAssignTargetVisitor {
+    fn check_target(&mut self, target: &ast::Expr) {
+        match &target.node {
+            ast::ExprKind::Subscript { value, .. } | ast::ExprKind::Attribute { value, .. } if is_temporary(value) => {
+                let end = target.end_location.filter(|end| end.row() == target.location.row()).unwrap_or(target.location);
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "Assignment to a temporary object has no effect".to_string(),
+                    line: target.location.row(),
+                    column: target.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(target.location.column() + 1),
+                    path: String::new(),
+                    code: "W0138".to_string(),
+                });
+            }
+            ast::ExprKind::Tuple { elts, .. } | ast::ExprKind::List { elts, .. } => {
+                for elt in elts {
+                    self.check_target(elt);
+                }
+            }
+            ast::ExprKind::Starred { value, .. } => self.check_target(value),
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for SuspiciousAssignTargetVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::Assign { targets, .. } => {
+                for target in targets {
+                    self.check_target(target);
+                }
+            }
+            ast::StmtKind::AugAssign { target, .. } | ast::StmtKind::AnnAssign { target, .. } => self.check_target(target),
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for SuspiciousAssignTarget {
+    fn code(&self) -> &'static str {
+        "W0138"
+    }
+
+    fn name(&self) -> &'static str {
+        "assignment-to-temporary"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags assignments to an item or attribute of a literal container, which is discarded right away."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = SuspiciousAssignTargetVisitor::default();
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use std::collections::HashSet;
+
+pub struct TabIndentation {
+    use_tabs: bool,
+}
+
+impl TabIndentation {
+    pub fn new(use_tabs: bool) -> Self {
+        Self { use_tabs }
+    }
+}
+
+impl super::Rule for TabIndentation {
+    fn code(&self) -> &'static str {
+        "W191"
+    }
+
+    fn name(&self) -> &'static str {
+        "tab-indentation"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags tabs in code, or spaces in indentation when `use_tabs` is set."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        // Lines that begin inside a string literal
+        let string_lines: HashSet<usize> =
+            context.string_ranges().iter().flat_map(|&(start, end)| start + 1..=end).collect();
+
+        let mut diagnostics = Vec::new();
+        for (i, &line) in context.lines.iter().enumerate() {
+            let line_num = i + 1;
+            let indentation = &line[..line.len() - line.trim_start_matches([' ', '\t']).len()];
+
+            // Tab-indented projects get the inverse check
+            let offender = if self.use_tabs {
+                indentation
+                    .find(' ')
+                    .filter(|_| !line.trim().is_empty())
+                    .map(|index| (index, "Indentation contains spaces (use tabs instead)"))
+            } else if string_lines.contains(&line_num) {
+                // Tabs inside a multi-line string are part of its value
+                None
+            } else {
+                line.find('\t').map(|index| (index, "Line contains tabs (use spaces instead)"))
+            };
+            if let Some((index, message)) = offender {
+                let column = line[..index].chars().count() + 1;
+                diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: message.to_string(),
+                    line: line_num,
+                    column,
+                    end_line: line_num,
+                    end_column: column,
+                    path: String::new(),
+                    code: "W191".to_string(),
+                });
+            }
+        }
+
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+
+pub struct TrailingBlankLines;
+
+impl super::Rule for TrailingBlankLines {
+    fn code(&self) -> &'static str {
+        "W391"
+    }
+
+    fn name(&self) -> &'static str {
+        "blank-line-at-end-of-file"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags blank lines after the last line of code."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+
+        // Empty files have nothing to trail, and a missing final newline is W292's
+        if context.source.trim().is_empty() || !context.source.ends_with('\n') {
+            return Ok(diagnostics);
+        }
+
+        let trailing_blank = context.lines.iter().rev().take_while(|line| line.trim().is_empty()).count();
+        if trailing_blank > 0 {
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: format!(
+                    "Blank line{} at end of file",
+                    if trailing_blank == 1 { "" } else { "s" }
+                ),
+                line: context.lines.len() - trailing_blank + 1,
+                column: 1,
+                end_line: context.lines.len() - trailing_blank + 1,
+                end_column: 1,
+                path: String::new(),
+                code: "W391".to_string(),
+            });
+        }
+
+        Ok(diagnostics)
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::config::TrailingCommaStyle;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct TrailingComma {
+    style: TrailingCommaStyle,
+}
+
+impl TrailingComma {
+    pub fn new(style: TrailingCommaStyle) -> Self {
+        Self { style }
+    }
+}
+
+/// What follows the last element of a bracketed list. Rows are 1-based and
+/// columns 0-based, as in the AST.
+struct Trailing {
+    comma: Option<(usize, usize)>,
+    close_row: usize,
+}
+
+/// Scans forward from `(row, column)`, just past the last element, over
+/// whitespace, comments and at most one comma to the closing bracket.
+fn trailing(lines: &[&str], row: usize, column: usize) -> Option<Trailing> {
+    let mut comma = None;
+    for (index, line) in lines.iter().enumerate().skip(row - 1) {
+        let start = if index == row - 1 { column } else { 0 };
+        for (i, c) in line.chars().enumerate().skip(start) {
+            match c {
+                ' ' | '\t' | '\r' | '\\' => {}
+                '#' => break,
+                ',' if comma.is_none() => comma = Some((index + 1, i)),
+                ')' | ']' | '}' => return Some(Trailing { comma, close_row: index + 1 }),
+                // Anything else means the element didn't end where the AST says,
+                // e.g. it was wrapped in parentheses of its own
+                _ => return None,
+            }
+        }
+    }
+    None
+}
+
+struct TrailingCommaVisitor<'a> {
+    style: TrailingCommaStyle,
+    lines: &'a [&'a str],
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl TrailingCommaVisitor<'_> {
+    fn starts_with_paren(&self, expr: &ast::Expr) -> bool {
+        let line = self.lines.get(expr.location.row() - 1);
+        line.and_then(|line| line.chars().nth(expr.location.column())) == Some('(')
+    }
+
+    /// Checks a bracketed list whose last element ends at `end`.
+    fn check(&mut self, end: ast::Location) {
+        let Some(trailing) = trailing(self.lines, end.row(), end.column()) else {
+            return;
+        };
+        // Only brackets closed on a later line than the last element
+        if trailing.close_row == end.row() {
+            return;
+        }
+        let (message, line, column) = match (self.style, trailing.comma) {
+            (TrailingCommaStyle::Require, None) => ("Missing trailing comma", end.row(), end.column()),
+            (TrailingCommaStyle::Forbid, Some((line, column))) => ("Unexpected trailing comma", line, column),
+            _ => return,
+        };
+        self.diagnostics.push(Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: message.to_string(),
+            line,
+            column: column + 1,
+            end_line: line,
+            end_column: column + 1,
+            path: String::new(),
+            code: "COM812".to_string(),
+        });
+    }
+}
+
+fn end(expr: &ast::Expr) -> ast::Location {
+    expr.end_location.unwrap_or(expr.location)
+}
+
+/// Where the last call argument ends. A lone generator argument can't take a comma.
+fn arguments_end(args: &[ast::Expr], keywords: &[ast::Keyword]) -> Option<ast::Location> {
+    if let [arg] = args {
+        if keywords.is_empty() && matches!(arg.node, ast::ExprKind::GeneratorExp { .. }) {
+            return None;
+        }
+    }
+    args.iter().map(end).chain(keywords.iter().map(|keyword| end(&keyword.node.value))).max()
+}
+
+/// Where the last parameter ends, including its annotation and default.
+fn parameters_end(args: &ast::Arguments) -> Option<ast::Location> {
+    let params = args
+        .posonlyargs
+        .iter()
+        .chain(&args.args)
+        .chain(args.vararg.as_deref())
+        .chain(&args.kwonlyargs)
+        .chain(args.kwarg.as_deref())
+        .map(|arg| arg.end_location.unwrap_or(arg.location));
+    let defaults = args.defaults.iter().chain(&args.kw_defaults).map(end);
+    params.chain(defaults).max()
+}
+
+impl Visitor for TrailingCommaVisitor<'_> {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::FunctionDef { args, .. } | ast::StmtKind::AsyncFunctionDef { args, .. } = &stmt.node {
+            if let Some(end) = parameters_end(args) {
+                self.check(end);
+            }
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        let last = match &expr.node {
+            ast::ExprKind::Call { args, keywords, .. } => arguments_end(args, keywords),
+            ast::ExprKind::List { elts, .. } | ast::ExprKind::Set { elts } => elts.last().map(end),
+            // Unparenthesized tuples have no brackets, and `(x,)` needs its comma
+            ast::ExprKind::Tuple { elts, .. } if elts.len() > 1 && self.starts_with_paren(expr) => elts.last().map(end),
+            ast::ExprKind::Dict { values, .. } => values.last().map(end),
+            _ => None,
+        };
+        if let Some(last) = last {
+            self.check(last);
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl super::Rule for TrailingComma {
+    fn code(&self) -> &'static str {
+        "COM812"
+    }
+
+    fn name(&self) -> &'static str {
+        "trailing-comma"
+    }
+
+    fn description(&self) -> &'static str {
+        "Requires or forbids, per the 'trailing_comma' setting, a comma after the last element of calls, definitions and collections spanning several lines."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        if self.style == TrailingCommaStyle::Off {
+            return Ok(Vec::new());
+        }
+        let mut visitor = TrailingCommaVisitor { style: self.style, lines: &context.lines, diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use crate::fix::Edit;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use std::collections::HashSet;
+
+pub struct TrailingWhitespace;
+
+impl TrailingWhitespace {
+    /// Returns `(line, column where the whitespace starts, line length)` in characters.
+    fn find(context: &LintContext) -> Vec<(usize, usize, usize)> {
+        // Lines whose end falls inside a multi-line string literal, where trailing
+        // whitespace is part of the string's value. The closing line ends with code
+        // after the quotes, so it's still checked
+        let strings: HashSet<usize> = context.string_ranges().iter().flat_map(|&(start, end)| start..end).collect();
+
+        context
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let trimmed = line.trim_end();
+                if trimmed.len() < line.len() && !strings.contains(&(i + 1)) {
+                    Some((i + 1, trimmed.chars().count() + 1, line.chars().count()))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl super::Rule for TrailingWhitespace {
+    fn code(&self) -> &'static str {
+        "W291"
+    }
+
+    fn name(&self) -> &'static str {
+        "trailing-whitespace"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags whitespace at the end of a line."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        Ok(Self::find(context)
+            .into_iter()
+            .map(|(line, column, length)| Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: "Trailing whitespace".to_string(),
+                line,
+                column,
+                end_line: line,
+                end_column: length,
+                path: String::new(),
+                code: "W291".to_string(),
+            })
+            .collect())
+    }
+
+    fn fix(&self, context: &LintContext) -> Result<Option<Vec<Edit>>> {
+        Ok(Some(
+            Self::find(context)
+                .into_iter()
+                .map(|(line, column, length)| Edit {
+                    start_line: line,
+                    start_col: column,
+                    end_line: line,
+                    end_col: length + 1,
+                    replacement: String::new(),
+                })
+                .collect(),
+        ))
+    }
+}
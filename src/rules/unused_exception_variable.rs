@@ -0,0 +1,90 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+
+pub struct UnusedExceptionVariable;
+
+struct UnusedExceptionVariableVisitor<'a> {
+    lines: &'a [&'a str],
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Whether `name` is read (or deleted) anywhere in the visited code,
+/// including closures that capture it.
+struct NameUse<'a> {
+    name: &'a str,
+    used: bool,
+}
+
+impl Visitor for NameUse<'_> {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Name { id, ctx } = &expr.node {
+            if id == self.name && !matches!(ctx, ast::ExprContext::Store) {
+                self.used = true;
+            }
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+impl UnusedExceptionVariableVisitor<'_> {
+    /// The 0-based column of the name in `as name` on the handler's first
+    /// line, since the AST doesn't locate the name itself.
+    fn name_column(&self, handler: &ast::Excepthandler, name: &str) -> Option<usize> {
+        let line = self.lines.get(handler.location.row() - 1)?;
+        let (offset, _) = line
+            .match_indices(name)
+            .filter(|&(i, _)| line[..i].trim_end().ends_with("as"))
+            .last()?;
+        Some(line[..offset].chars().count())
+    }
+}
+
+impl Visitor for UnusedExceptionVariableVisitor<'_> {
+    fn visit_excepthandler(&mut self, handler: &ast::Excepthandler) {
+        let ast::ExcepthandlerKind::ExceptHandler { name: Some(name), body, .. } = &handler.node else {
+            return visitor::walk_excepthandler(self, handler);
+        };
+        if !name.starts_with('_') {
+            let mut usage = NameUse { name, used: false };
+            visitor::walk_suite(&mut usage, body);
+            if !usage.used {
+                let line = handler.location.row();
+                let column = self.name_column(handler, name).unwrap_or(handler.location.column());
+                self.diagnostics.push(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: format!("Exception variable '{}' is never used", name),
+                    line,
+                    column: column + 1,
+                    end_line: line,
+                    end_column: column + name.chars().count(),
+                    path: String::new(),
+                    code: "W0139".to_string(),
+                });
+            }
+        }
+        visitor::walk_excepthandler(self, handler);
+    }
+}
+
+impl super::Rule for UnusedExceptionVariable {
+    fn code(&self) -> &'static str {
+        "W0139"
+    }
+
+    fn name(&self) -> &'static str {
+        "unused-exception-variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags 'except ... as e' handlers that never use 'e'; drop the binding or name it with a leading underscore."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = UnusedExceptionVariableVisitor { lines: &context.lines, diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -1,148 +1,254 @@
 use anyhow::Result;
 use rustpython_parser::ast;
-use crate::linter::Diagnostic;
+use crate::linter::{Diagnostic, LintContext};
 use crate::linter::DiagnosticLevel;
+use crate::fix::Edit;
+use super::visitor::{self, Visitor};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::Regex;
 use std::collections::HashSet;
+use std::sync::OnceLock;
 
-pub struct UnusedImports;
+/// Unused imports, except in files matching the `allow_unused_in` globs, where
+/// imports are usually re-exports, and on lines marked `# noqa: F401`.
+#[derive(Default)]
+pub struct UnusedImports {
+    allow_unused_in: GlobSet,
+}
 
 impl UnusedImports {
-    fn collect_used_names(&self, expr: &ast::Expr, used_names: &mut HashSet<String>) {
+    /// Allows unused imports in files matching `globs`. Globs use `.gitignore`
+    /// syntax and are matched against the path as linted; invalid ones are
+    /// skipped with a warning.
+    pub fn new(globs: &[String]) -> Self {
+        let mut builder = GlobSetBuilder::new();
+        for glob in globs {
+            match GlobBuilder::new(glob).literal_separator(true).build() {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(e) => log::warn!("Ignoring invalid allow_unused_in glob '{}': {}", glob, e),
+            }
+        }
+        let allow_unused_in = builder.build().unwrap_or_else(|e| {
+            log::warn!("Ignoring allow_unused_in: {}", e);
+            GlobSet::empty()
+        });
+        Self { allow_unused_in }
+    }
+}
+
+/// A `# noqa` comment, optionally limited to codes as in `# noqa: F401, E501`.
+fn noqa_comment() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"(?i)#\s*noqa(?::\s*([A-Z]+[0-9]+(?:[,\s]+[A-Z]+[0-9]+)*))?").unwrap())
+}
+
+/// Whether `line` has a `# noqa` comment covering F401.
+fn is_noqa(line: &str) -> bool {
+    noqa_comment().captures(line).is_some_and(|captures| match captures.get(1) {
+        Some(codes) => codes.as_str().split([',', ' ', '\t']).any(|code| code.eq_ignore_ascii_case("F401")),
+        None => true,
+    })
+}
+
+/// Collects every name and attribute referenced anywhere in a module.
+#[derive(Default)]
+struct UsedNames {
+    names: HashSet<String>,
+}
+
+/// String entries of a list or tuple literal, e.g. `["foo", "bar"]`.
+fn string_entries(expr: &ast::Expr) -> Vec<String> {
+    match &expr.node {
+        ast::ExprKind::List { elts, .. } | ast::ExprKind::Tuple { elts, .. } => elts
+            .iter()
+            .filter_map(|elt| match &elt.node {
+                ast::ExprKind::Constant { value: ast::Constant::Str(s), .. } => Some(s.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_dunder_all(target: &ast::Expr) -> bool {
+    matches!(&target.node, ast::ExprKind::Name { id, .. } if id == "__all__")
+}
+
+impl Visitor for UsedNames {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        // Names exported through `__all__` count as used
+        let exported = match &stmt.node {
+            ast::StmtKind::Assign { targets, value, .. } if targets.iter().any(is_dunder_all) => Some(value),
+            ast::StmtKind::AugAssign { target, value, .. }
+            | ast::StmtKind::AnnAssign { target, value: Some(value), .. } if is_dunder_all(target) => Some(value),
+            _ => None,
+        };
+        if let Some(value) = exported {
+            self.names.extend(string_entries(value));
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
         match &expr.node {
             ast::ExprKind::Name { id, .. } => {
-                used_names.insert(id.to_string());
+                self.names.insert(id.to_string());
             }
             ast::ExprKind::Attribute { value, attr, .. } => {
                 // For attributes like sys.path, collect both sys and path
-                self.collect_used_names(value, used_names);
-                used_names.insert(attr.to_string());
+                self.names.insert(attr.to_string());
                 // Also collect the full path (e.g., "sys.path")
                 if let ast::ExprKind::Name { id, .. } = &value.node {
-                    used_names.insert(format!("{}.{}", id, attr));
-                }
-            }
-            ast::ExprKind::Call { func, args, .. } => {
-                self.collect_used_names(func, used_names);
-                for arg in args {
-                    self.collect_used_names(arg, used_names);
+                    self.names.insert(format!("{}.{}", id, attr));
                 }
             }
-            ast::ExprKind::BinOp { left, right, .. } => {
-                self.collect_used_names(left, used_names);
-                self.collect_used_names(right, used_names);
-            }
-            ast::ExprKind::Subscript { value, slice, .. } => {
-                self.collect_used_names(value, used_names);
-                self.collect_used_names(slice, used_names);
-            }
             _ => {}
         }
+        visitor::walk_expr(self, expr);
     }
+}
 
-    fn collect_used_names_from_stmt(&self, stmt: &ast::Stmt, used_names: &mut HashSet<String>) {
-        match &stmt.node {
-            ast::StmtKind::FunctionDef { body, args, .. } => {
-                // Process function arguments
-                for arg in &args.args {
-                    if let Some(annotation) = &arg.node.annotation {
-                        self.collect_used_names(annotation, used_names);
-                    }
-                }
-                // Process function body
-                for stmt in body {
-                    self.collect_used_names_from_stmt(stmt, used_names);
-                }
-            }
-            ast::StmtKind::Assign { targets, value, .. } => {
-                for target in targets {
-                    self.collect_used_names(target, used_names);
-                }
-                self.collect_used_names(value, used_names);
-            }
-            ast::StmtKind::AnnAssign { target, annotation, value, .. } => {
-                self.collect_used_names(target, used_names);
-                self.collect_used_names(annotation, used_names);
-                if let Some(val) = value {
-                    self.collect_used_names(val, used_names);
-                }
-            }
-            ast::StmtKind::Return { value, .. } => {
-                if let Some(val) = value {
-                    self.collect_used_names(val, used_names);
+/// An imported name that is never referenced, with the 1-based span of the
+/// alias (end column inclusive).
+struct UnusedImport {
+    name: String,
+    asname: Option<String>,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+    stmt_index: usize,
+}
+
+impl UnusedImports {
+    /// Returns every import that is never used and not allowed to be.
+    fn find_unused(&self, context: &LintContext) -> Vec<UnusedImport> {
+        // Paths given as `./pkg/...` should match the same globs as `pkg/...`
+        let path = context.path.strip_prefix(".").unwrap_or(context.path);
+        if !path.as_os_str().is_empty() && self.allow_unused_in.is_match(path) {
+            return Vec::new();
+        }
+        let ast = context.ast;
+        let mut defined_imports = Vec::new();
+
+        // First pass: collect all imports
+        for (index, stmt) in ast.iter().enumerate() {
+            let names = match &stmt.node {
+                ast::StmtKind::Import { names } => names,
+                // Relative imports (`from . import x`, `from .pkg import x`) bind the
+                // imported names just like absolute ones; the module itself is never bound
+                ast::StmtKind::ImportFrom { names, .. } => names,
+                _ => continue,
+            };
+            // Wildcard imports are reported by the StarImport rule instead
+            for alias in names.iter().filter(|alias| alias.node.name != "*") {
+                if context.lines.get(alias.location.row() - 1).is_some_and(|line| is_noqa(line)) {
+                    continue;
                 }
+                let end = alias.end_location.unwrap_or(alias.location);
+                defined_imports.push(UnusedImport {
+                    name: alias.node.name.to_string(),
+                    asname: alias.node.asname.clone(),
+                    line: alias.location.row(),
+                    column: alias.location.column() + 1,
+                    end_line: end.row(),
+                    end_column: end.column().max(alias.location.column() + 1),
+                    stmt_index: index,
+                });
             }
-            ast::StmtKind::Expr { value } => {
-                self.collect_used_names(value, used_names);
-            }
-            _ => {}
         }
+
+        // Second pass: collect used names from every nested statement and expression
+        let mut used = UsedNames::default();
+        visitor::walk_suite(&mut used, ast);
+        let used_names = used.names;
+
+        defined_imports
+            .into_iter()
+            .filter(|UnusedImport { name, asname, .. }| {
+                let is_used = if let Some(alias) = asname {
+                    used_names.contains(alias)
+                } else {
+                    let name_parts: Vec<&str> = name.split('.').collect();
+                    let base_name = name_parts[0];
+                    used_names.contains(name) ||
+                    used_names.contains(base_name) ||
+                    used_names.iter().any(|used| used.starts_with(&format!("{}.", base_name)))
+                };
+                !is_used
+            })
+            .collect()
     }
 }
 
 impl super::Rule for UnusedImports {
-    fn check(&self, ast: &ast::Suite, _source: &str) -> Result<Vec<Diagnostic>> {
+    fn code(&self) -> &'static str {
+        "F401"
+    }
+
+    fn name(&self) -> &'static str {
+        "unused-import"
+    }
+
+    fn description(&self) -> &'static str {
+        "Detects imported modules and names that are never used."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
         let mut diagnostics = Vec::new();
-        let mut used_names = HashSet::new();
-        let mut defined_imports = Vec::new();
 
-        // First pass: collect all imports
-        for stmt in ast.iter() {
-            match &stmt.node {
-                ast::StmtKind::Import { names } => {
-                    for alias in names {
-                        let import_name = match &alias.node.asname {
-                            Some(asname) => (alias.node.name.to_string(), stmt.location.row(), Some(asname.to_string())),
-                            None => (alias.node.name.to_string(), stmt.location.row(), None),
-                        };
-                        defined_imports.push(import_name);
-                    }
-                }
-                ast::StmtKind::ImportFrom { module: Some(_module), names, .. } => {
-                    for alias in names {
-                        let import_name = match &alias.node.asname {
-                            Some(asname) => (alias.node.name.to_string(), stmt.location.row(), Some(asname.to_string())),
-                            None => (alias.node.name.to_string(), stmt.location.row(), None),
-                        };
-                        defined_imports.push(import_name);
-                    }
-                }
-                _ => {}
-            }
+        for unused in self.find_unused(context) {
+            let display_name = if let Some(alias) = &unused.asname {
+                format!("{} as {}", unused.name, alias)
+            } else {
+                unused.name
+            };
+            diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: format!("Unused import '{}'", display_name),
+                line: unused.line,
+                column: unused.column,
+                end_line: unused.end_line,
+                end_column: unused.end_column,
+                path: String::new(),
+                code: "F401".to_string(),
+            });
         }
 
-        // Second pass: collect used names
-        for stmt in ast.iter() {
-            self.collect_used_names_from_stmt(stmt, &mut used_names);
-        }
+        Ok(diagnostics)
+    }
 
-        // Check for unused imports
-        for (name, line, asname) in defined_imports {
-            let is_used = if let Some(ref alias) = asname {
-                used_names.contains(alias)
-            } else {
-                let name_parts: Vec<&str> = name.split('.').collect();
-                let base_name = name_parts[0];
-                used_names.contains(&name) || 
-                used_names.contains(base_name) ||
-                used_names.iter().any(|used| used.starts_with(&format!("{}.", base_name)))
+    fn fix(&self, context: &LintContext) -> Result<Option<Vec<Edit>>> {
+        let unused = self.find_unused(context);
+        let lines = &context.lines;
+        let mut edits = Vec::new();
+
+        for (index, stmt) in context.ast.iter().enumerate() {
+            let imported = match &stmt.node {
+                ast::StmtKind::Import { names } | ast::StmtKind::ImportFrom { names, .. } => {
+                    names.iter().filter(|alias| alias.node.name != "*").count()
+                }
+                _ => continue,
             };
+            let unused_here = unused.iter().filter(|import| import.stmt_index == index).count();
+            if imported == 0 || unused_here != imported {
+                // Partially used statements would need rewriting rather than deleting
+                continue;
+            }
 
-            if !is_used {
-                let display_name = if let Some(alias) = asname {
-                    format!("{} as {}", name, alias)
-                } else {
-                    name
-                };
-                diagnostics.push(Diagnostic {
-                    level: DiagnosticLevel::Warning,
-                    message: format!("Unused import '{}'", display_name),
-                    line,
-                    column: 1,
-                    path: String::new(),
-                });
+            // Only delete statements that sit alone on a single line
+            let row = stmt.location.row();
+            let single_line = stmt.end_location.is_none_or(|end| end.row() == row);
+            let alone = lines
+                .get(row - 1)
+                .is_some_and(|line| line.trim_start().len() + stmt.location.column() == line.len() && !line.contains(';'));
+            if single_line && alone {
+                edits.push(Edit::delete_line(row));
             }
         }
 
-        Ok(diagnostics)
+        Ok(Some(edits))
     }
 }
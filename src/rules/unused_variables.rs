@@ -0,0 +1,141 @@
+use anyhow::Result;
+use rustpython_parser::ast;
+use crate::linter::{Diagnostic, LintContext};
+use crate::linter::DiagnosticLevel;
+use super::visitor::{self, Visitor};
+use std::collections::HashSet;
+
+pub struct UnusedVariables;
+
+/// Records the locals bound in one function body, without descending into
+/// nested scopes.
+#[derive(Default)]
+struct LocalAssignments {
+    assigned: Vec<(String, ast::Location)>,
+    augmented: HashSet<String>,
+    declared_outer: HashSet<String>,
+}
+
+impl Visitor for LocalAssignments {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        match &stmt.node {
+            ast::StmtKind::FunctionDef { .. }
+            | ast::StmtKind::AsyncFunctionDef { .. }
+            | ast::StmtKind::ClassDef { .. } => return,
+            ast::StmtKind::Assign { targets, .. } => {
+                for target in targets {
+                    if let ast::ExprKind::Name { id, .. } = &target.node {
+                        self.assigned.push((id.to_string(), target.location));
+                    }
+                }
+            }
+            ast::StmtKind::AnnAssign { target, value: Some(_), .. } => {
+                if let ast::ExprKind::Name { id, .. } = &target.node {
+                    self.assigned.push((id.to_string(), target.location));
+                }
+            }
+            // `x += 1` reads x, so it counts as a use
+            ast::StmtKind::AugAssign { target, .. } => {
+                if let ast::ExprKind::Name { id, .. } = &target.node {
+                    self.augmented.insert(id.to_string());
+                }
+            }
+            ast::StmtKind::Global { names } | ast::StmtKind::Nonlocal { names } => {
+                self.declared_outer.extend(names.iter().cloned());
+            }
+            _ => {}
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Lambda { .. } = &expr.node {
+            return;
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+/// Records every name that is read, including from nested closures.
+#[derive(Default)]
+struct LoadedNames {
+    names: HashSet<String>,
+}
+
+impl Visitor for LoadedNames {
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        if let ast::ExprKind::Name { id, ctx: ast::ExprContext::Load } = &expr.node {
+            self.names.insert(id.to_string());
+        }
+        visitor::walk_expr(self, expr);
+    }
+}
+
+struct UnusedVariablesVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl UnusedVariablesVisitor {
+    fn check_function(&mut self, body: &[ast::Stmt]) {
+        let mut assignments = LocalAssignments::default();
+        visitor::walk_suite(&mut assignments, body);
+        let mut loaded = LoadedNames::default();
+        visitor::walk_suite(&mut loaded, body);
+
+        // Anything may be read through locals(), so don't guess
+        if loaded.names.contains("locals") {
+            return;
+        }
+
+        let mut reported = HashSet::new();
+        for (name, location) in assignments.assigned {
+            if name.starts_with('_')
+                || loaded.names.contains(&name)
+                || assignments.augmented.contains(&name)
+                || assignments.declared_outer.contains(&name)
+                || !reported.insert(name.clone())
+            {
+                continue;
+            }
+            self.diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Warning,
+                message: format!("Local variable '{}' is assigned to but never used", name),
+                line: location.row(),
+                column: location.column() + 1,
+                end_line: location.row(),
+                end_column: location.column() + 1,
+                path: String::new(),
+                code: "F841".to_string(),
+            });
+        }
+    }
+}
+
+impl Visitor for UnusedVariablesVisitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        if let ast::StmtKind::FunctionDef { body, .. } | ast::StmtKind::AsyncFunctionDef { body, .. } = &stmt.node {
+            self.check_function(body);
+        }
+        visitor::walk_stmt(self, stmt);
+    }
+}
+
+impl super::Rule for UnusedVariables {
+    fn code(&self) -> &'static str {
+        "F841"
+    }
+
+    fn name(&self) -> &'static str {
+        "unused-variable"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags local variables that are assigned inside a function but never read."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        let mut visitor = UnusedVariablesVisitor { diagnostics: Vec::new() };
+        visitor::walk_suite(&mut visitor, context.ast);
+        Ok(visitor.diagnostics)
+    }
+}
@@ -0,0 +1,328 @@
+use rustpython_parser::ast;
+
+/// Depth-first AST traversal shared by the rules. Override the `visit_*`
+/// methods you care about and call the matching `walk_*` function to keep
+/// descending into children.
+pub trait Visitor {
+    fn visit_stmt(&mut self, stmt: &ast::Stmt) {
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &ast::Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_excepthandler(&mut self, handler: &ast::Excepthandler) {
+        walk_excepthandler(self, handler);
+    }
+
+    fn visit_arguments(&mut self, arguments: &ast::Arguments) {
+        walk_arguments(self, arguments);
+    }
+
+    fn visit_comprehension(&mut self, comprehension: &ast::Comprehension) {
+        walk_comprehension(self, comprehension);
+    }
+
+    fn visit_pattern(&mut self, pattern: &ast::Pattern) {
+        walk_pattern(self, pattern);
+    }
+}
+
+pub fn walk_suite<V: Visitor + ?Sized>(visitor: &mut V, suite: &[ast::Stmt]) {
+    for stmt in suite {
+        visitor.visit_stmt(stmt);
+    }
+}
+
+pub fn walk_stmt<V: Visitor + ?Sized>(visitor: &mut V, stmt: &ast::Stmt) {
+    match &stmt.node {
+        ast::StmtKind::FunctionDef { args, body, decorator_list, returns, .. }
+        | ast::StmtKind::AsyncFunctionDef { args, body, decorator_list, returns, .. } => {
+            for decorator in decorator_list {
+                visitor.visit_expr(decorator);
+            }
+            visitor.visit_arguments(args);
+            if let Some(returns) = returns {
+                visitor.visit_expr(returns);
+            }
+            walk_suite(visitor, body);
+        }
+        ast::StmtKind::ClassDef { bases, keywords, body, decorator_list, .. } => {
+            for decorator in decorator_list {
+                visitor.visit_expr(decorator);
+            }
+            for base in bases {
+                visitor.visit_expr(base);
+            }
+            for keyword in keywords {
+                visitor.visit_expr(&keyword.node.value);
+            }
+            walk_suite(visitor, body);
+        }
+        ast::StmtKind::Return { value } => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        ast::StmtKind::Delete { targets } => {
+            for target in targets {
+                visitor.visit_expr(target);
+            }
+        }
+        ast::StmtKind::Assign { targets, value, .. } => {
+            for target in targets {
+                visitor.visit_expr(target);
+            }
+            visitor.visit_expr(value);
+        }
+        ast::StmtKind::AugAssign { target, value, .. } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(value);
+        }
+        ast::StmtKind::AnnAssign { target, annotation, value, .. } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(annotation);
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        ast::StmtKind::For { target, iter, body, orelse, .. }
+        | ast::StmtKind::AsyncFor { target, iter, body, orelse, .. } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(iter);
+            walk_suite(visitor, body);
+            walk_suite(visitor, orelse);
+        }
+        ast::StmtKind::While { test, body, orelse } | ast::StmtKind::If { test, body, orelse } => {
+            visitor.visit_expr(test);
+            walk_suite(visitor, body);
+            walk_suite(visitor, orelse);
+        }
+        ast::StmtKind::With { items, body, .. } | ast::StmtKind::AsyncWith { items, body, .. } => {
+            for item in items {
+                visitor.visit_expr(&item.context_expr);
+                if let Some(vars) = &item.optional_vars {
+                    visitor.visit_expr(vars);
+                }
+            }
+            walk_suite(visitor, body);
+        }
+        ast::StmtKind::Match { subject, cases } => {
+            visitor.visit_expr(subject);
+            for case in cases {
+                visitor.visit_pattern(&case.pattern);
+                if let Some(guard) = &case.guard {
+                    visitor.visit_expr(guard);
+                }
+                walk_suite(visitor, &case.body);
+            }
+        }
+        ast::StmtKind::Raise { exc, cause } => {
+            if let Some(exc) = exc {
+                visitor.visit_expr(exc);
+            }
+            if let Some(cause) = cause {
+                visitor.visit_expr(cause);
+            }
+        }
+        ast::StmtKind::Try { body, handlers, orelse, finalbody } => {
+            walk_suite(visitor, body);
+            for handler in handlers {
+                visitor.visit_excepthandler(handler);
+            }
+            walk_suite(visitor, orelse);
+            walk_suite(visitor, finalbody);
+        }
+        ast::StmtKind::Assert { test, msg } => {
+            visitor.visit_expr(test);
+            if let Some(msg) = msg {
+                visitor.visit_expr(msg);
+            }
+        }
+        ast::StmtKind::Expr { value } => {
+            visitor.visit_expr(value);
+        }
+        ast::StmtKind::Import { .. }
+        | ast::StmtKind::ImportFrom { .. }
+        | ast::StmtKind::Global { .. }
+        | ast::StmtKind::Nonlocal { .. }
+        | ast::StmtKind::Pass
+        | ast::StmtKind::Break
+        | ast::StmtKind::Continue => {}
+    }
+}
+
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expr: &ast::Expr) {
+    match &expr.node {
+        ast::ExprKind::BoolOp { values, .. } => {
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        ast::ExprKind::NamedExpr { target, value } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(value);
+        }
+        ast::ExprKind::BinOp { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        ast::ExprKind::UnaryOp { operand, .. } => {
+            visitor.visit_expr(operand);
+        }
+        ast::ExprKind::Lambda { args, body } => {
+            visitor.visit_arguments(args);
+            visitor.visit_expr(body);
+        }
+        ast::ExprKind::IfExp { test, body, orelse } => {
+            visitor.visit_expr(test);
+            visitor.visit_expr(body);
+            visitor.visit_expr(orelse);
+        }
+        ast::ExprKind::Dict { keys, values } => {
+            for key in keys {
+                visitor.visit_expr(key);
+            }
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        ast::ExprKind::Set { elts }
+        | ast::ExprKind::List { elts, .. }
+        | ast::ExprKind::Tuple { elts, .. } => {
+            for elt in elts {
+                visitor.visit_expr(elt);
+            }
+        }
+        ast::ExprKind::ListComp { elt, generators }
+        | ast::ExprKind::SetComp { elt, generators }
+        | ast::ExprKind::GeneratorExp { elt, generators } => {
+            for comprehension in generators {
+                visitor.visit_comprehension(comprehension);
+            }
+            visitor.visit_expr(elt);
+        }
+        ast::ExprKind::DictComp { key, value, generators } => {
+            for comprehension in generators {
+                visitor.visit_comprehension(comprehension);
+            }
+            visitor.visit_expr(key);
+            visitor.visit_expr(value);
+        }
+        ast::ExprKind::Await { value } | ast::ExprKind::YieldFrom { value } => {
+            visitor.visit_expr(value);
+        }
+        ast::ExprKind::Yield { value } => {
+            if let Some(value) = value {
+                visitor.visit_expr(value);
+            }
+        }
+        ast::ExprKind::Compare { left, comparators, .. } => {
+            visitor.visit_expr(left);
+            for comparator in comparators {
+                visitor.visit_expr(comparator);
+            }
+        }
+        ast::ExprKind::Call { func, args, keywords } => {
+            visitor.visit_expr(func);
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+            for keyword in keywords {
+                visitor.visit_expr(&keyword.node.value);
+            }
+        }
+        ast::ExprKind::FormattedValue { value, format_spec, .. } => {
+            visitor.visit_expr(value);
+            if let Some(format_spec) = format_spec {
+                visitor.visit_expr(format_spec);
+            }
+        }
+        ast::ExprKind::JoinedStr { values } => {
+            for value in values {
+                visitor.visit_expr(value);
+            }
+        }
+        ast::ExprKind::Attribute { value, .. } | ast::ExprKind::Starred { value, .. } => {
+            visitor.visit_expr(value);
+        }
+        ast::ExprKind::Subscript { value, slice, .. } => {
+            visitor.visit_expr(value);
+            visitor.visit_expr(slice);
+        }
+        ast::ExprKind::Slice { lower, upper, step } => {
+            for part in [lower, upper, step].into_iter().flatten() {
+                visitor.visit_expr(part);
+            }
+        }
+        ast::ExprKind::Constant { .. } | ast::ExprKind::Name { .. } => {}
+    }
+}
+
+pub fn walk_excepthandler<V: Visitor + ?Sized>(visitor: &mut V, handler: &ast::Excepthandler) {
+    let ast::ExcepthandlerKind::ExceptHandler { type_, body, .. } = &handler.node;
+    if let Some(type_) = type_ {
+        visitor.visit_expr(type_);
+    }
+    walk_suite(visitor, body);
+}
+
+pub fn walk_arguments<V: Visitor + ?Sized>(visitor: &mut V, arguments: &ast::Arguments) {
+    let all_args = arguments
+        .posonlyargs
+        .iter()
+        .chain(&arguments.args)
+        .chain(arguments.vararg.as_deref())
+        .chain(&arguments.kwonlyargs)
+        .chain(arguments.kwarg.as_deref());
+    for arg in all_args {
+        if let Some(annotation) = &arg.node.annotation {
+            visitor.visit_expr(annotation);
+        }
+    }
+    for default in arguments.defaults.iter().chain(&arguments.kw_defaults) {
+        visitor.visit_expr(default);
+    }
+}
+
+pub fn walk_comprehension<V: Visitor + ?Sized>(visitor: &mut V, comprehension: &ast::Comprehension) {
+    visitor.visit_expr(&comprehension.iter);
+    visitor.visit_expr(&comprehension.target);
+    for condition in &comprehension.ifs {
+        visitor.visit_expr(condition);
+    }
+}
+
+pub fn walk_pattern<V: Visitor + ?Sized>(visitor: &mut V, pattern: &ast::Pattern) {
+    match &pattern.node {
+        ast::PatternKind::MatchValue { value } => {
+            visitor.visit_expr(value);
+        }
+        ast::PatternKind::MatchSequence { patterns } | ast::PatternKind::MatchOr { patterns } => {
+            for pattern in patterns {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        ast::PatternKind::MatchMapping { keys, patterns, .. } => {
+            for key in keys {
+                visitor.visit_expr(key);
+            }
+            for pattern in patterns {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        ast::PatternKind::MatchClass { cls, patterns, kwd_patterns, .. } => {
+            visitor.visit_expr(cls);
+            for pattern in patterns.iter().chain(kwd_patterns) {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        ast::PatternKind::MatchAs { pattern, .. } => {
+            if let Some(pattern) = pattern {
+                visitor.visit_pattern(pattern);
+            }
+        }
+        ast::PatternKind::MatchSingleton { .. } | ast::PatternKind::MatchStar { .. } => {}
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use rustlint::baseline::Baseline;
+use rustlint::processor::process_files;
+use rustlint::rules::RuleRegistry;
+use tempfile::tempdir;
+
+#[test]
+fn test_baseline_suppresses_existing_diagnostics() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("legacy.py");
+    std::fs::write(&file, "import os\nimport sys\n")?;
+    let rules = RuleRegistry::with_defaults();
+
+    let diagnostics = process_files(vec![file.clone()], &rules)?;
+    assert!(!diagnostics.is_empty());
+    let baseline_path = dir.path().join("baseline.json");
+    Baseline::from_diagnostics(&diagnostics).save(&baseline_path)?;
+
+    let mut relinted = process_files(vec![file], &rules)?;
+    Baseline::load(&baseline_path)?.filter(&mut relinted);
+    assert!(relinted.is_empty(), "baselined diagnostics still reported: {:?}", relinted);
+    Ok(())
+}
+
+#[test]
+fn test_baseline_survives_shifted_lines_and_reports_new_violations() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("legacy.py");
+    std::fs::write(&file, "import os\n")?;
+    let rules = RuleRegistry::with_defaults();
+
+    let baseline = Baseline::from_diagnostics(&process_files(vec![file.clone()], &rules)?);
+
+    // The old violation moves down a line and a new one appears above it
+    std::fs::write(&file, "import json\nimport os\n")?;
+    let mut diagnostics = process_files(vec![file], &rules)?;
+    baseline.filter(&mut diagnostics);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Unused import 'json'");
+    Ok(())
+}
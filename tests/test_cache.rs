@@ -0,0 +1,106 @@
+use anyhow::Result;
+use rustlint::cache::Cache;
+use rustlint::config::Config;
+use rustlint::linter::{Diagnostic, DiagnosticLevel, LintContext};
+use rustlint::processor::process_files_with_cache;
+use rustlint::rules::{Rule, RuleRegistry};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::tempdir;
+
+/// Reports one diagnostic per file and counts how often it actually runs.
+struct CountingRule {
+    runs: Arc<AtomicUsize>,
+}
+
+impl Rule for CountingRule {
+    fn code(&self) -> &'static str {
+        "T001"
+    }
+
+    fn name(&self) -> &'static str {
+        "counting"
+    }
+
+    fn description(&self) -> &'static str {
+        "Counts invocations."
+    }
+
+    fn check(&self, _context: &LintContext) -> Result<Vec<Diagnostic>> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: "counted".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 1,
+            path: String::new(),
+            code: "T001".to_string(),
+        }])
+    }
+}
+
+#[test]
+fn test_second_run_served_from_cache() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("module.py");
+    std::fs::write(&file, "x = 1\n")?;
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let mut rules = RuleRegistry::new();
+    rules.register(Box::new(CountingRule { runs: runs.clone() }));
+    let cache = Cache::new(dir.path().join(".rustlint_cache"), &Config::default(), &rules)?;
+
+    let first = process_files_with_cache(vec![file.clone()], &rules, Some(&cache))?;
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+    let second = process_files_with_cache(vec![file.clone()], &rules, Some(&cache))?;
+    assert_eq!(runs.load(Ordering::SeqCst), 1, "second run should be served from cache");
+    assert_eq!(first.len(), second.len());
+    assert_eq!(second[0].path, file.to_string_lossy());
+    assert_eq!(second[0].message, "counted");
+
+    // Changing the content misses the cache
+    std::fs::write(&file, "x = 2\n")?;
+    process_files_with_cache(vec![file.clone()], &rules, Some(&cache))?;
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+    // So does changing the config
+    let mut config = Config::default();
+    config.rules.max_line_length = 100;
+    let cache = Cache::new(dir.path().join(".rustlint_cache"), &config, &rules)?;
+    process_files_with_cache(vec![file.clone()], &rules, Some(&cache))?;
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+    // And turning the directory into a package, which INP001 checks
+    std::fs::write(dir.path().join("__init__.py"), "")?;
+    process_files_with_cache(vec![file], &rules, Some(&cache))?;
+    assert_eq!(runs.load(Ordering::SeqCst), 4);
+
+    Ok(())
+}
+
+#[test]
+fn test_identical_content_at_allowlisted_path() -> Result<()> {
+    let dir = tempdir()?;
+    let package = dir.path().join("pkg");
+    std::fs::create_dir(&package)?;
+    let init = package.join("__init__.py");
+    let module = package.join("mod.py");
+    std::fs::write(&init, "import os\n")?;
+    std::fs::write(&module, "import os\n")?;
+
+    let config = Config::default();
+    let rules = RuleRegistry::from_config(&config).filter(&["F401".to_string()], &[]);
+    let cache = Cache::new(dir.path().join(".rustlint_cache"), &config, &rules)?;
+
+    // The package facade is allowlisted, but the same import elsewhere is not
+    assert!(process_files_with_cache(vec![init], &rules, Some(&cache))?.is_empty());
+    let diagnostics = process_files_with_cache(vec![module.clone()], &rules, Some(&cache))?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "F401");
+    assert_eq!(diagnostics[0].path, module.to_string_lossy());
+
+    Ok(())
+}
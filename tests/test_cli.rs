@@ -0,0 +1,297 @@
+use anyhow::Result;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+use tempfile::tempdir;
+
+fn run_with_stdin(args: &[&str], stdin: &str) -> Result<Output> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rustlint"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().unwrap().write_all(stdin.as_bytes())?;
+    Ok(child.wait_with_output()?)
+}
+
+#[test]
+fn test_lint_stdin() -> Result<()> {
+    let output = run_with_stdin(&["--stdin-filename", "buffer.py", "-"], "import os\nprint('hi')\n")?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(stdout.contains("Unused import 'os'"), "stdout: {}", stdout);
+    assert!(stdout.contains("buffer.py:1"), "stdout: {}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_lint_stdin_with_files() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("on_disk.py");
+    std::fs::write(&file, "import sys\n")?;
+
+    let output = run_with_stdin(&["-", file.to_str().unwrap()], "import os\n")?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(stdout.contains("Unused import 'os' at <stdin>:1"), "stdout: {}", stdout);
+    assert!(stdout.contains("Unused import 'sys'"), "stdout: {}", stdout);
+    assert!(stdout.contains("on_disk.py:1"), "stdout: {}", stdout);
+    Ok(())
+}
+
+#[test]
+fn test_exit_status() -> Result<()> {
+    let dir = tempdir()?;
+    let broken = dir.path().join("broken.py");
+    std::fs::write(&broken, "def f(:\n    pass\n")?;
+    let warned = dir.path().join("warned.py");
+    std::fs::write(&warned, "import os\n")?;
+    let (broken, warned) = (broken.to_str().unwrap(), warned.to_str().unwrap());
+
+    let output = run_with_stdin(&[broken], "")?;
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_with_stdin(&["--exit-zero", broken], "")?;
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_with_stdin(&[warned], "")?;
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_with_stdin(&["--error-on-warning", warned], "")?;
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_with_stdin(&["--error-on-warning", "--exit-zero", warned], "")?;
+    assert_eq!(output.status.code(), Some(0));
+
+    Ok(())
+}
+
+#[test]
+fn test_explain() -> Result<()> {
+    let output = run_with_stdin(&["--explain", "E501"], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(output.status.success());
+    assert!(stdout.contains("E501 (line-too-long)"), "stdout: {}", stdout);
+    assert!(stdout.contains("maximum line length"), "stdout: {}", stdout);
+
+    let output = run_with_stdin(&["--explain", "X999"], "")?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[test]
+fn test_summary_and_statistics() -> Result<()> {
+    let dir = tempdir()?;
+    let first = dir.path().join("first.py");
+    std::fs::write(&first, "import os\nimport sys\n")?;
+    let second = dir.path().join("second.py");
+    std::fs::write(&second, "import json\ndef f(:\n")?;
+    let (first, second) = (first.to_str().unwrap(), second.to_str().unwrap());
+
+    let output = run_with_stdin(&["--no-cache", "--select", "F401", "--statistics", first, second], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // Two unused imports in first.py, and a syntax error in second.py
+    assert!(stdout.contains("Found 2 warnings and 1 error across 2 files"), "stdout: {}", stdout);
+    let table: Vec<_> = stdout
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let count = parts.next()?.parse::<usize>().ok()?;
+            Some((parts.next()?.to_string(), count))
+        })
+        .collect();
+    assert_eq!(table, vec![("F401".to_string(), 2), ("E999".to_string(), 1)]);
+
+    let output = run_with_stdin(&["--no-cache", "--quiet", "--select", "F401", "--statistics", first, second], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("Found"), "stdout: {}", stdout);
+    assert!(!stdout.contains("Unused import 'os'"), "stdout: {}", stdout);
+    assert!(stdout.contains("Syntax error"), "stdout: {}", stdout);
+    assert_eq!(stdout.lines().count(), 1, "stdout: {}", stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_quiet_hides_warnings() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("warnings.py");
+    std::fs::write(&file, "import os
+")?;
+
+    let output = run_with_stdin(&["--no-cache", "--quiet", file.to_str().unwrap()], "")?;
+    assert!(output.stdout.is_empty(), "stdout: {}", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(output.status.code(), Some(0));
+
+    // Hidden warnings still fail the run when asked to
+    let output = run_with_stdin(&["--no-cache", "--quiet", "--error-on-warning", file.to_str().unwrap()], "")?;
+    assert!(output.stdout.is_empty());
+    assert_eq!(output.status.code(), Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_verbose_logs_each_file() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("module.py");
+    std::fs::write(&file, "x = 1
+")?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustlint"))
+        .args(["--no-cache", "--verbose", file.to_str().unwrap()])
+        .env_remove("RUST_LOG")
+        .output()?;
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("Linting") && stderr.contains("module.py"), "stderr: {}", stderr);
+
+    Ok(())
+}
+
+#[test]
+fn test_json_stdout_is_clean() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("messy.py");
+    std::fs::write(&file, "import os\n\ndef BadName():\n    camelCase = 1\n    return camelCase\n\n\nclass lower_class:\n    pass\n")?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustlint"))
+        .args(["--no-cache", "--format", "json", file.to_str().unwrap()])
+        .env_remove("RUST_LOG")
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // Nothing but the JSON document may reach stdout
+    let diagnostics: Vec<rustlint::linter::Diagnostic> = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("stdout is not clean JSON ({}): {}", e, stdout));
+    assert!(diagnostics.iter().any(|d| d.code == "N802"));
+    assert!(diagnostics.iter().any(|d| d.code == "F401"));
+    Ok(())
+}
+
+#[test]
+fn test_no_color() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("module.py");
+    std::fs::write(&file, "import os\n")?;
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rustlint"))
+        .args(["--no-cache", file.to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Unused import 'os'"), "stdout: {}", stdout);
+    assert!(!stdout.contains('\x1b'), "stdout: {:?}", stdout);
+
+    // An explicit flag still forces color
+    let output = Command::new(env!("CARGO_BIN_EXE_rustlint"))
+        .args(["--no-cache", "--color", "always", file.to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .output()?;
+    assert!(String::from_utf8(output.stdout)?.contains('\x1b'));
+
+    Ok(())
+}
+
+#[test]
+fn test_max_diagnostics() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("imports.py");
+    std::fs::write(&file, "import os\nimport sys\nimport json\nimport re\nimport abc\n")?;
+    let file = file.to_str().unwrap();
+
+    let output = run_with_stdin(&["--no-cache", "--select", "F401", "--max-diagnostics", "2", file], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let reported: Vec<_> = stdout.lines().filter(|line| line.contains("Unused import")).collect();
+    assert_eq!(reported.len(), 2, "stdout: {}", stdout);
+    assert!(reported[0].contains("'os'") && reported[1].contains("'sys'"), "stdout: {}", stdout);
+    assert!(stdout.contains("... and 3 more (use --max-diagnostics 0 for all)"), "stdout: {}", stdout);
+    // The summary still counts everything
+    assert!(stdout.contains("Found 5 warnings"), "stdout: {}", stdout);
+
+    let output = run_with_stdin(&["--no-cache", "--select", "F401", "--max-diagnostics", "0", file], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(stdout.lines().filter(|line| line.contains("Unused import")).count(), 5);
+    assert!(!stdout.contains("more (use"));
+
+    Ok(())
+}
+
+#[test]
+fn test_timing_lists_every_rule() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("timed.py");
+    std::fs::write(&file, "import os\n\n\ndef f(x):\n    return x\n")?;
+    let config = dir.path().join("rustlint.toml");
+    std::fs::write(&config, "")?;
+
+    let output = run_with_stdin(&["--timing", "--config", config.to_str().unwrap(), file.to_str().unwrap()], "")?;
+    let stderr = String::from_utf8(output.stderr)?;
+
+    let reported: Vec<_> = stderr.lines().filter(|line| line.ends_with("ms")).collect();
+    let rules = rustlint::rules::list_rules();
+    assert_eq!(reported.len(), rules.len(), "stderr: {}", stderr);
+    for rule in rules {
+        let prefix = format!("{} ({}): ", rule.code, rule.name);
+        assert!(reported.iter().any(|line| line.starts_with(&prefix)), "missing {}: {}", rule.code, stderr);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_diff_previews_fixes() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("fixable.py");
+    let source = "import os\nimport sys\n\nprint(sys.argv)\n";
+    std::fs::write(&file, source)?;
+
+    let output = run_with_stdin(&["--diff", "--fix", file.to_str().unwrap()], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(stdout.contains("\n-import os\n import sys\n"), "stdout: {}", stdout);
+    assert!(!stdout.contains("Unused import"), "stdout: {}", stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(std::fs::read_to_string(&file)?, source);
+    Ok(())
+}
+
+#[test]
+fn test_output_format_from_config() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("module.py");
+    std::fs::write(&file, "import os\n")?;
+    let config = dir.path().join("rustlint.toml");
+    std::fs::write(&config, "[output]\nformat = \"json\"\n")?;
+    let args = ["--config", config.to_str().unwrap(), file.to_str().unwrap()];
+
+    let output = run_with_stdin(&args, "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let diagnostics: Vec<rustlint::linter::Diagnostic> = serde_json::from_str(&stdout)
+        .map_err(|e| anyhow::anyhow!("{}: {}", e, stdout))?;
+    assert!(diagnostics.iter().any(|d| d.code == "F401"));
+
+    // The flag wins over the config
+    let output = run_with_stdin(&[&args[..], &["--format", "text"]].concat(), "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Unused import 'os'"), "stdout: {}", stdout);
+    assert!(serde_json::from_str::<serde_json::Value>(&stdout).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_fix_only_selected_codes() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("fixable.py");
+    std::fs::write(&file, "import os\nimport sys   \n\nprint(sys.argv)\n")?;
+
+    let output = run_with_stdin(&["--fix-only", "F401", file.to_str().unwrap()], "")?;
+    let stdout = String::from_utf8(output.stdout)?;
+
+    // The import is gone but the trailing whitespace is left, and still reported
+    assert_eq!(std::fs::read_to_string(&file)?, "import sys   \n\nprint(sys.argv)\n");
+    assert!(!stdout.contains("Unused import"), "stdout: {}", stdout);
+    assert!(stdout.contains("Trailing whitespace"), "stdout: {}", stdout);
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use rustlint::fix::{apply_edits, diff_file, fix_file, fix_source, unified_diff, Edit};
+use rustlint::rules::{get_default_rules, Rule};
+use std::fs;
+use tempfile::tempdir;
+
+fn default_rules() -> Vec<Box<dyn Rule + Sync>> {
+    get_default_rules()
+        .into_iter()
+        .map(|r| r as Box<dyn Rule + Sync>)
+        .collect()
+}
+
+#[test]
+fn test_fix_unused_import() -> Result<()> {
+    let dir = tempdir()?;
+    let path = dir.path().join("example.py");
+    fs::write(&path, "import os\nimport sys\n\nprint(sys.argv)\n")?;
+
+    let applied = fix_file(&path, &default_rules())?;
+
+    assert_eq!(applied, 1);
+    assert_eq!(fs::read_to_string(&path)?, "import sys\n\nprint(sys.argv)\n");
+    Ok(())
+}
+
+#[test]
+fn test_fix_trailing_whitespace_and_unused_import_on_same_line() -> Result<()> {
+    let source = "import os   \nx = 1  \nprint(x)\n";
+    let (fixed, applied) = fix_source(source, "example.py", &default_rules())?;
+
+    assert_eq!(fixed, "x = 1\nprint(x)\n");
+    assert_eq!(applied, 3);
+    Ok(())
+}
+
+#[test]
+fn test_fix_leaves_partially_used_import() -> Result<()> {
+    let source = "import os, sys\nprint(sys.argv)\n";
+    let (fixed, applied) = fix_source(source, "example.py", &default_rules())?;
+
+    assert_eq!(fixed, source);
+    assert_eq!(applied, 0);
+    Ok(())
+}
+
+#[test]
+fn test_fix_keeps_allowed_unused_imports() -> Result<()> {
+    let dir = tempdir()?;
+    let init = dir.path().join("__init__.py");
+    fs::write(&init, "from .models import User\n")?;
+    let module = dir.path().join("module.py");
+    fs::write(&module, "import os  # noqa: F401\nimport re\n")?;
+
+    assert_eq!(fix_file(&init, &default_rules())?, 0);
+    assert_eq!(fix_file(&module, &default_rules())?, 1);
+    assert_eq!(fs::read_to_string(&module)?, "import os  # noqa: F401\n");
+    Ok(())
+}
+
+#[test]
+fn test_apply_overlapping_edits_prefers_later_column() {
+    let source = "abcdef\n";
+    let edits = vec![
+        Edit { start_line: 1, start_col: 2, end_line: 1, end_col: 5, replacement: "X".to_string() },
+        Edit { start_line: 1, start_col: 4, end_line: 1, end_col: 6, replacement: "Y".to_string() },
+    ];
+
+    let (fixed, applied) = apply_edits(source, &edits);
+
+    assert_eq!(fixed, "abcYf\n");
+    assert_eq!(applied, 1);
+}
+
+#[test]
+fn test_delete_last_line_without_newline() {
+    let (fixed, applied) = apply_edits("x = 1\nimport os", &[Edit::delete_line(2)]);
+    assert_eq!(fixed, "x = 1\n");
+    assert_eq!(applied, 1);
+}
+
+#[test]
+fn test_diff_file_leaves_file_untouched() -> Result<()> {
+    let dir = tempdir()?;
+    let path = dir.path().join("example.py");
+    let source = "import os\nimport sys\n\nprint(sys.argv)\n";
+    fs::write(&path, source)?;
+
+    let (diff, pending) = diff_file(&path, &default_rules())?;
+
+    assert_eq!(pending, 1);
+    let name = path.to_string_lossy();
+    assert_eq!(diff, format!("--- a/{0}\n+++ b/{0}\n@@ -1,4 +1,3 @@\n-import os\n import sys\n \n print(sys.argv)\n", name));
+    assert_eq!(fs::read_to_string(&path)?, source);
+    Ok(())
+}
+
+#[test]
+fn test_unified_diff_splits_distant_changes() {
+    let old: String = (1..=12).map(|i| format!("line{}\n", i)).collect();
+    let new = old.replace("line2\n", "").replace("line11\n", "line11  \n");
+
+    assert_eq!(unified_diff("f.py", &old, &old), "");
+    assert_eq!(
+        unified_diff("f.py", &old, &new),
+        "--- a/f.py\n+++ b/f.py\n\
+         @@ -1,5 +1,4 @@\n line1\n-line2\n line3\n line4\n line5\n\
+         @@ -8,5 +7,5 @@\n line8\n line9\n line10\n-line11\n+line11  \n line12\n"
+    );
+    assert_eq!(
+        unified_diff("f.py", "x = 1", "x = 1\n"),
+        "--- a/f.py\n+++ b/f.py\n@@ -1,1 +1,1 @@\n-x = 1\n\\ No newline at end of file\n+x = 1\n"
+    );
+}
@@ -1,4 +1,4 @@
-use rustlint::{config::Config, linter::{Diagnostic, DiagnosticLevel}};
+use rustlint::{config::Config, linter::DiagnosticLevel};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -101,3 +101,364 @@ fn test_diagnostic_level() {
     assert_eq!(DiagnosticLevel::Error, DiagnosticLevel::Error);
     assert_eq!(DiagnosticLevel::Warning, DiagnosticLevel::Warning);
 }
+
+#[test]
+fn test_configured_max_line_length() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config_path = dir.path().join("rustlint.toml");
+    std::fs::write(&config_path, "[rules]\nmax_line_length = 120\nmax_complexity = 15\n")?;
+
+    let source = format!("x = '{}'\n", "a".repeat(94));
+    assert_eq!(source.trim_end().chars().count(), 100);
+
+    // The default limit of 88 flags the line
+    let linter = rustlint::linter::Linter::new(Config::default());
+    let diagnostics = linter.lint_source(&source, &PathBuf::from("long.py"))?;
+    assert!(diagnostics.iter().any(|d| d.message.contains("Line too long")));
+
+    // A configured limit of 120 suppresses it, both in the linter and the rule set
+    let config = Config::from_file(&config_path)?;
+    assert_eq!(config.rules.max_line_length, 120);
+    assert_eq!(config.rules.max_complexity, 15);
+    assert_eq!(Config::default().rules.max_complexity, 10);
+
+    let file_path = dir.path().join("long.py");
+    std::fs::write(&file_path, &source)?;
+    let rules: Vec<Box<dyn rustlint::rules::Rule + Sync>> = rustlint::rules::get_rules(&config)
+        .into_iter()
+        .map(|r| r as Box<dyn rustlint::rules::Rule + Sync>)
+        .collect();
+    let diagnostics = rustlint::linter::lint_file(&file_path, &rules)?;
+    assert!(!diagnostics.iter().any(|d| d.message.contains("Line too long")));
+
+    let linter = rustlint::linter::Linter::new(config);
+    let diagnostics = linter.lint_source(&source, &file_path)?;
+    assert!(!diagnostics.iter().any(|d| d.message.contains("Line too long")));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_discovery() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let nested = dir.path().join("src/pkg");
+    std::fs::create_dir_all(&nested)?;
+
+    // No config anywhere falls back to the defaults
+    assert!(Config::discover(&nested).is_none());
+    let config = Config::load(None, &nested)?;
+    assert_eq!(config.rules.max_line_length, 88);
+
+    // A config in a parent directory is picked up from nested directories
+    std::fs::write(dir.path().join("rustlint.toml"), "[rules]\nmax_line_length = 100\n")?;
+    assert_eq!(Config::discover(&nested), Some(dir.path().join("rustlint.toml")));
+    let config = Config::load(None, &nested)?;
+    assert_eq!(config.rules.max_line_length, 100);
+
+    // An explicit path wins over discovery
+    let explicit = dir.path().join("custom.toml");
+    std::fs::write(&explicit, "[rules]\nmax_line_length = 79\n")?;
+    let config = Config::load(Some(&explicit), &nested)?;
+    assert_eq!(config.rules.max_line_length, 79);
+
+    Ok(())
+}
+
+#[test]
+fn test_pyproject_config() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let nested = dir.path().join("src/pkg");
+    std::fs::create_dir_all(&nested)?;
+
+    // A pyproject.toml without a [tool.rustlint] table is ignored
+    let pyproject = dir.path().join("pyproject.toml");
+    std::fs::write(&pyproject, "[project]\nname = \"demo\"\n")?;
+    assert!(Config::discover(&nested).is_none());
+
+    std::fs::write(
+        &pyproject,
+        "[project]\nname = \"demo\"\n\n[tool.rustlint.rules]\nmax_line_length = 120\n\n[tool.black]\nline-length = 120\n",
+    )?;
+    assert_eq!(Config::discover(&nested), Some(pyproject.clone()));
+    let config = Config::load(None, &nested)?;
+    assert_eq!(config.rules.max_line_length, 120);
+    assert_eq!(config.rules.max_complexity, 10);
+
+    // The override is applied when linting
+    let linter = rustlint::linter::Linter::new(config);
+    let source = format!("x = \"{}\"\n", "a".repeat(100));
+    let diagnostics = linter.lint_source(&source, &nested.join("wide.py"))?;
+    assert!(!diagnostics.iter().any(|d| d.code == "E501"));
+
+    // A rustlint.toml in the same directory takes precedence
+    std::fs::write(dir.path().join("rustlint.toml"), "[rules]\nmax_line_length = 100\n")?;
+    assert_eq!(Config::load(None, &nested)?.rules.max_line_length, 100);
+
+    // And so does an explicit --config, which may also point at a pyproject.toml
+    let explicit = nested.join("pyproject.toml");
+    std::fs::write(&explicit, "[tool.rustlint.rules]\nmax_line_length = 79\n")?;
+    assert_eq!(Config::load(Some(&explicit), dir.path())?.rules.max_line_length, 79);
+
+    Ok(())
+}
+
+#[test]
+fn test_syntax_error_location() -> Result<()> {
+    let source = "x = 1\n\ndef f(:\n    pass\n";
+
+    let linter = rustlint::linter::Linter::new(Config::default());
+    let diagnostics = linter.lint_source(source, &PathBuf::from("broken.py"))?;
+    let error = diagnostics
+        .iter()
+        .find(|d| d.level == DiagnosticLevel::Error)
+        .expect("Should report a syntax error");
+    assert_eq!(error.line, 3);
+    assert_eq!(error.column, 7);
+    assert!(error.message.starts_with("Syntax error"));
+
+    // The rule-based path reports the same location
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("broken.py");
+    std::fs::write(&path, source)?;
+    let diagnostics = rustlint::linter::lint_file(&path, &[])?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 3);
+    assert_eq!(diagnostics[0].column, 7);
+
+    Ok(())
+}
+
+#[test]
+fn test_long_line_reported_once() -> Result<()> {
+    let linter = rustlint::linter::Linter::new(Config::default());
+    let source = format!("x = '{}'\n", "a".repeat(100));
+    let diagnostics = linter.lint_source(&source, &PathBuf::from("long.py"))?;
+
+    let line_length: Vec<_> = diagnostics.iter().filter(|d| d.code == "E501").collect();
+    assert_eq!(line_length.len(), 1, "{:?}", diagnostics);
+    assert!(line_length[0].message.contains("Line too long"));
+
+    Ok(())
+}
+
+#[test]
+fn test_severity_overrides() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config_path = dir.path().join("rustlint.toml");
+    std::fs::write(&config_path, "[rules.severity]\nF401 = \"error\"\nW291 = \"off\"\n")?;
+    let config = Config::from_file(&config_path)?;
+
+    let linter = rustlint::linter::Linter::new(config);
+    let diagnostics = linter.lint_source("import os   \n", &PathBuf::from("example.py"))?;
+
+    let unused = diagnostics.iter().find(|d| d.code == "F401").expect("unused import reported");
+    assert_eq!(unused.level, DiagnosticLevel::Error);
+    assert!(!diagnostics.iter().any(|d| d.code == "W291"));
+
+    Ok(())
+}
+
+#[test]
+fn test_escalate_repeated_warnings() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config_path = dir.path().join("rustlint.toml");
+    std::fs::write(&config_path, "[rules.escalate]\nF401 = 5\nW291 = 5\n")?;
+    let config = Config::from_file(&config_path)?;
+    let linter = rustlint::linter::Linter::new(config);
+
+    let source = "import os\nimport re\nimport io\nimport abc\nimport csv\nimport json   \n";
+    let diagnostics = linter.lint_source(source, &PathBuf::from("example.py"))?;
+    let unused: Vec<_> = diagnostics.iter().filter(|d| d.code == "F401").collect();
+    assert_eq!(unused.len(), 6);
+    assert!(unused.iter().all(|d| d.level == DiagnosticLevel::Error));
+    // A single trailing whitespace stays below its threshold
+    let trailing = diagnostics.iter().find(|d| d.code == "W291").expect("trailing whitespace reported");
+    assert_eq!(trailing.level, DiagnosticLevel::Warning);
+
+    // Below the threshold, unused imports stay warnings
+    let diagnostics = linter.lint_source("import os\nimport re\n", &PathBuf::from("example.py"))?;
+    assert!(diagnostics.iter().filter(|d| d.code == "F401").all(|d| d.level == DiagnosticLevel::Warning));
+
+    Ok(())
+}
+
+#[test]
+fn test_lint_str() -> Result<()> {
+    let rules: Vec<Box<dyn rustlint::rules::Rule + Sync>> = rustlint::rules::get_default_rules()
+        .into_iter()
+        .map(|r| r as Box<dyn rustlint::rules::Rule + Sync>)
+        .collect();
+    let diagnostics = rustlint::linter::lint_str("import os\n", "buffer.py", &rules)?;
+
+    let unused = diagnostics.iter().find(|d| d.code == "F401").expect("unused import reported");
+    assert_eq!(unused.path, "buffer.py");
+    assert!(diagnostics.iter().all(|d| d.path == "buffer.py"));
+
+    Ok(())
+}
+
+#[test]
+fn test_configured_indent_size() -> Result<()> {
+    let source = "def f():\n  return 1\n";
+
+    let linter = rustlint::linter::Linter::new(Config::default());
+    let diagnostics = linter.lint_source(source, &PathBuf::from("two.py"))?;
+    let indentation: Vec<_> = diagnostics.iter().filter(|d| d.code == "E111").collect();
+    assert_eq!(indentation.len(), 1);
+    assert_eq!(indentation[0].message, "Indentation of 2 spaces should be a multiple of 4");
+
+    let mut config = Config::default();
+    config.rules.indent_size = 2;
+    let linter = rustlint::linter::Linter::new(config);
+    let diagnostics = linter.lint_source(source, &PathBuf::from("two.py"))?;
+    assert!(!diagnostics.iter().any(|d| d.code == "E111"), "{:?}", diagnostics);
+
+    Ok(())
+}
+
+#[test]
+fn test_use_tabs() -> Result<()> {
+    let mut config = Config::default();
+    config.rules.use_tabs = true;
+    let linter = rustlint::linter::Linter::new(config);
+    let diagnostics = linter.lint_source("def f():\n\treturn 1\n\ndef g():\n    return 2\n", &PathBuf::from("tabs.py"))?;
+
+    let tabs: Vec<_> = diagnostics.iter().filter(|d| d.code == "W191").map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(tabs, vec![(5, "Indentation contains spaces (use tabs instead)")]);
+    assert!(!diagnostics.iter().any(|d| d.code == "E111"));
+
+    Ok(())
+}
+
+#[test]
+fn test_continuation_lines_not_checked_for_indentation() -> Result<()> {
+    let linter = rustlint::linter::Linter::new(Config::default());
+    let source = r#"def describe(name,
+             greeting):
+    """Describe someone.
+
+      Indented prose in a docstring is fine.
+    """
+    return format_message(name,
+                          greeting,
+                          extra=True)
+
+
+value = compute(1,
+  2)
+"#;
+    let diagnostics = linter.lint_source(source, &PathBuf::from("continuation.py"))?;
+    assert!(!diagnostics.iter().any(|d| d.code == "E111"), "{:?}", diagnostics);
+
+    // The first line of a statement is still checked
+    let diagnostics = linter.lint_source("if True:\n   x = call(1,\n            2)\n", &PathBuf::from("bad.py"))?;
+    let indentation: Vec<_> = diagnostics.iter().filter(|d| d.code == "E111").map(|d| d.line).collect();
+    assert_eq!(indentation, vec![2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_tabs_inside_strings_not_reported() -> Result<()> {
+    let linter = rustlint::linter::Linter::new(Config::default());
+    let source = "TABLE = \"\"\"\nname\tvalue\n\tindented\n\"\"\"\nif TABLE:\n\tpass\n";
+    let diagnostics = linter.lint_source(source, &PathBuf::from("table.py"))?;
+
+    let tabs: Vec<_> = diagnostics.iter().filter(|d| d.code == "W191").map(|d| d.line).collect();
+    assert_eq!(tabs, vec![6]);
+
+    Ok(())
+}
+
+#[test]
+fn test_diagnostic_fingerprint() {
+    let diagnostic = |line: usize, message: &str| rustlint::linter::Diagnostic {
+        level: DiagnosticLevel::Warning,
+        message: message.to_string(),
+        line,
+        column: 8,
+        end_line: line,
+        end_column: 9,
+        path: "example.py".to_string(),
+        code: "F401".to_string(),
+    };
+    let context = "import os";
+
+    assert_eq!(
+        diagnostic(1, "Unused import 'os'").fingerprint(context),
+        diagnostic(12, "Unused import 'os'").fingerprint(context)
+    );
+    assert_eq!(
+        diagnostic(1, "Unused import 'os'").fingerprint(context),
+        diagnostic(1, "Unused import 'os'").fingerprint("    import   os")
+    );
+    assert_ne!(
+        diagnostic(1, "Unused import 'os'").fingerprint(context),
+        diagnostic(1, "Unused import 'sys'").fingerprint(context)
+    );
+
+    // Line numbers quoted in the message don't change the fingerprint, but names do
+    let context = "def foo():";
+    assert_eq!(
+        diagnostic(7, "Redefinition of function 'foo' (previous definition on line 4)").fingerprint(context),
+        diagnostic(8, "Redefinition of function 'foo' (previous definition on line 5)").fingerprint(context)
+    );
+    assert_ne!(
+        diagnostic(1, "Unused import 'x1'").fingerprint(context),
+        diagnostic(1, "Unused import 'x2'").fingerprint(context)
+    );
+}
+
+/// Records the address of the context and of its line buffer each time it runs.
+struct ContextProbe {
+    seen: std::sync::Arc<std::sync::Mutex<Vec<(usize, usize, usize)>>>,
+}
+
+impl rustlint::rules::Rule for ContextProbe {
+    fn code(&self) -> &'static str {
+        "T002"
+    }
+
+    fn name(&self) -> &'static str {
+        "context-probe"
+    }
+
+    fn description(&self) -> &'static str {
+        "Records the context it is given."
+    }
+
+    fn check(&self, context: &rustlint::linter::LintContext) -> Result<Vec<rustlint::linter::Diagnostic>> {
+        let ranges = context.string_ranges().as_ptr() as usize;
+        self.seen.lock().unwrap().push((context as *const _ as usize, context.lines.as_ptr() as usize, ranges));
+        Ok(Vec::new())
+    }
+}
+
+#[test]
+fn test_rules_share_one_context() -> Result<()> {
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut rules = rustlint::rules::RuleRegistry::with_defaults();
+    rules.register(Box::new(ContextProbe { seen: seen.clone() }));
+    rules.register(Box::new(ContextProbe { seen: seen.clone() }));
+
+    let source = "import os\n\ndoc = \"\"\"\n  text  \n\"\"\"\nx = 1   \n";
+    let diagnostics = rustlint::linter::lint_str(source, "shared.py", &rules)?;
+
+    let probes = seen.lock().unwrap().clone();
+    assert_eq!(probes.len(), 2);
+    assert_eq!(probes[0], probes[1], "both rules should see the same context, lines and string ranges");
+
+    // The shared context gives the same results as checking each rule on its own
+    let ast = rustpython_parser::parser::parse_program(source, "shared.py")?;
+    let mut expected = Vec::new();
+    for rule in rules.iter() {
+        let context = rustlint::linter::LintContext::new(source, &ast);
+        expected.extend(rule.check(&context)?.into_iter().map(|d| (d.code, d.line, d.column)));
+    }
+    let actual: Vec<_> = diagnostics.into_iter().map(|d| (d.code, d.line, d.column)).collect();
+    assert_eq!(actual, expected);
+    assert!(actual.contains(&("W291".to_string(), 6, 6)));
+    assert!(!actual.iter().any(|(code, line, _)| code == "W291" && *line == 4));
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use rustlint::lsp;
+use rustlint::rules::{self, Rule};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+fn frame(message: &Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+/// Splits the server's output back into JSON messages.
+fn parse_frames(output: &str) -> Vec<Value> {
+    output
+        .split("Content-Length: ")
+        .filter(|chunk| !chunk.is_empty())
+        .map(|chunk| {
+            let (_, body) = chunk.split_once("\r\n\r\n").unwrap();
+            serde_json::from_str(body).unwrap()
+        })
+        .collect()
+}
+
+fn default_rules() -> Vec<Box<dyn Rule + Sync>> {
+    rules::get_default_rules()
+        .into_iter()
+        .map(|r| r as Box<dyn Rule + Sync>)
+        .collect()
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_on_did_open() -> Result<()> {
+    let input = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+        json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {"textDocument": {
+                "uri": "file:///tmp/example.py",
+                "languageId": "python",
+                "version": 1,
+                "text": "import os\n",
+            }},
+        }),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+        json!({"jsonrpc": "2.0", "method": "exit"}),
+    ]
+    .iter()
+    .map(frame)
+    .collect::<String>();
+
+    let mut output = Vec::new();
+    lsp::run(Cursor::new(input), &mut output, &default_rules(), &BTreeMap::new(), &BTreeMap::new())?;
+    let messages = parse_frames(&String::from_utf8(output)?);
+
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0]["id"], 1);
+    assert_eq!(messages[0]["result"]["capabilities"]["textDocumentSync"], 1);
+
+    let published = &messages[1];
+    assert_eq!(published["method"], "textDocument/publishDiagnostics");
+    assert_eq!(published["params"]["uri"], "file:///tmp/example.py");
+    let diagnostics = published["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0],
+        json!({
+            "range": {"start": {"line": 0, "character": 7}, "end": {"line": 0, "character": 9}},
+            "severity": 2,
+            "code": "F401",
+            "source": "rustlint",
+            "message": "Unused import 'os'",
+        })
+    );
+
+    assert_eq!(messages[2], json!({"jsonrpc": "2.0", "id": 2, "result": null}));
+    Ok(())
+}
+
+#[test]
+fn test_lsp_clears_diagnostics_on_did_close() -> Result<()> {
+    let input = frame(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didClose",
+        "params": {"textDocument": {"uri": "file:///tmp/example.py"}},
+    }));
+
+    let mut output = Vec::new();
+    lsp::run(Cursor::new(input), &mut output, &default_rules(), &BTreeMap::new(), &BTreeMap::new())?;
+    let messages = parse_frames(&String::from_utf8(output)?);
+
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["params"]["diagnostics"], json!([]));
+    Ok(())
+}
+
+#[test]
+fn test_lsp_applies_escalation() -> Result<()> {
+    let input = frame(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {"textDocument": {
+            "uri": "file:///tmp/example.py",
+            "languageId": "python",
+            "version": 1,
+            "text": "import os\nimport sys\n",
+        }},
+    }));
+
+    // Two unused imports reach a threshold of two, so both become errors
+    let escalate = BTreeMap::from([("F401".to_string(), 2)]);
+    let mut output = Vec::new();
+    lsp::run(Cursor::new(input), &mut output, &default_rules(), &BTreeMap::new(), &escalate)?;
+    let messages = parse_frames(&String::from_utf8(output)?);
+
+    let diagnostics = messages[0]["params"]["diagnostics"].as_array().unwrap();
+    let severities: Vec<_> = diagnostics.iter().map(|d| (d["code"].clone(), d["severity"].clone())).collect();
+    assert_eq!(severities, vec![(json!("F401"), json!(1)), (json!("F401"), json!(1))]);
+    Ok(())
+}
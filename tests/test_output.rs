@@ -0,0 +1,185 @@
+use anyhow::Result;
+use rustlint::linter::{Diagnostic, DiagnosticLevel};
+use rustlint::output::{render_snippet, to_github, to_json, to_junit, to_sarif, ColorChoice};
+use rustlint::rules::{get_default_rules, Rule};
+use std::path::Path;
+
+fn sample_diagnostics() -> Vec<Diagnostic> {
+    vec![
+        Diagnostic {
+            level: DiagnosticLevel::Warning,
+            message: "Unused import 'os'".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 1,
+            path: "example.py".to_string(),
+            code: "F401".to_string(),
+        },
+        Diagnostic {
+            level: DiagnosticLevel::Error,
+            message: "Syntax error: unexpected EOF".to_string(),
+            line: 3,
+            column: 5,
+            end_line: 3,
+            end_column: 5,
+            path: "broken.py".to_string(),
+            code: "E999".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn test_json_output_shape() -> Result<()> {
+    let json = to_json(&sample_diagnostics())?;
+    let value: serde_json::Value = serde_json::from_str(&json)?;
+
+    let items = value.as_array().expect("JSON output should be an array");
+    assert_eq!(items.len(), 2);
+
+    let first = items[0].as_object().unwrap();
+    let mut keys: Vec<_> = first.keys().map(|k| k.as_str()).collect();
+    keys.sort();
+    assert_eq!(keys, vec!["code", "column", "end_column", "end_line", "level", "line", "message", "path"]);
+
+    assert_eq!(items[0]["level"], "warning");
+    assert_eq!(items[0]["code"], "F401");
+    assert_eq!(items[1]["level"], "error");
+    assert_eq!(items[1]["line"], 3);
+    assert_eq!(items[1]["column"], 5);
+    assert_eq!(items[1]["path"], "broken.py");
+
+    Ok(())
+}
+
+#[test]
+fn test_json_round_trip() -> Result<()> {
+    let json = to_json(&sample_diagnostics())?;
+    let parsed: Vec<Diagnostic> = serde_json::from_str(&json)?;
+
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].level, DiagnosticLevel::Warning);
+    assert_eq!(parsed[0].message, "Unused import 'os'");
+    assert_eq!(parsed[1].level, DiagnosticLevel::Error);
+    assert_eq!(parsed[1].code, "E999");
+
+    Ok(())
+}
+
+#[test]
+fn test_sarif_output() -> Result<()> {
+    let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
+        .into_iter()
+        .map(|r| r as Box<dyn Rule + Sync>)
+        .collect();
+    let mut diagnostics = sample_diagnostics();
+    diagnostics[0].path = "/work/project/src/example.py".to_string();
+
+    let sarif = to_sarif(&diagnostics, &rules, Path::new("/work/project"))?;
+    let value: serde_json::Value = serde_json::from_str(&sarif)?;
+
+    assert!(value["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+    assert_eq!(value["version"], "2.1.0");
+
+    let run = &value["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "rustlint");
+    let rule_ids: Vec<_> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap())
+        .collect();
+    let expected: Vec<_> = rules.iter().map(|r| r.code()).collect();
+    assert_eq!(rule_ids, expected);
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "F401");
+    assert_eq!(results[0]["level"], "warning");
+    assert_eq!(results[0]["message"]["text"], "Unused import 'os'");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/example.py");
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 1);
+    assert_eq!(location["region"]["endLine"], 1);
+    assert_eq!(location["region"]["endColumn"], 2);
+    assert_eq!(results[1]["level"], "error");
+
+    Ok(())
+}
+
+#[test]
+fn test_info_level() -> Result<()> {
+    assert_ne!(DiagnosticLevel::Info, DiagnosticLevel::Warning);
+    assert_eq!(DiagnosticLevel::Info.to_string(), "info");
+    assert_ne!(DiagnosticLevel::Info.to_string(), DiagnosticLevel::Warning.to_string());
+
+    let mut diagnostics = sample_diagnostics();
+    diagnostics[0].level = DiagnosticLevel::Info;
+
+    let json: serde_json::Value = serde_json::from_str(&to_json(&diagnostics)?)?;
+    assert_eq!(json[0]["level"], "info");
+
+    let sarif: serde_json::Value = serde_json::from_str(&to_sarif(&diagnostics, &[], Path::new("."))?)?;
+    assert_eq!(sarif["runs"][0]["results"][0]["level"], "note");
+
+    Ok(())
+}
+
+#[test]
+fn test_github_output() {
+    let mut diagnostics = sample_diagnostics();
+    diagnostics[1].message = "Syntax error: 100% broken\nhere".to_string();
+
+    let output = to_github(&diagnostics);
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines, vec![
+        "::warning file=example.py,line=1,col=1::Unused import 'os'",
+        "::error file=broken.py,line=3,col=5::Syntax error: 100%25 broken%0Ahere",
+    ]);
+}
+
+#[test]
+fn test_render_snippet() {
+    assert_eq!(render_snippet("x = y == None", 10), "x = y == None\n         ^");
+
+    // A leading tab expands to eight columns, so the caret moves with it
+    assert_eq!(render_snippet("\tfoo(bar)", 6), "        foo(bar)\n            ^");
+
+    // Columns past the end of the line point just after it
+    assert_eq!(render_snippet("x = 1", 6), "x = 1\n     ^");
+}
+
+#[test]
+fn test_color_choice() {
+    assert!(ColorChoice::Auto.should_color(false, true));
+    assert!(!ColorChoice::Auto.should_color(false, false));
+    assert!(!ColorChoice::Auto.should_color(true, true));
+    assert!(ColorChoice::Always.should_color(true, false));
+    assert!(!ColorChoice::Never.should_color(false, true));
+}
+
+#[test]
+fn test_junit_output() {
+    let mut diagnostics = sample_diagnostics();
+    diagnostics.push(Diagnostic {
+        level: DiagnosticLevel::Warning,
+        message: "Comparison 'a < b' uses <\"quotes\"> & more".to_string(),
+        line: 3,
+        column: 4,
+        end_line: 3,
+        end_column: 8,
+        path: "example.py".to_string(),
+        code: "PLR0133".to_string(),
+    });
+    let files = vec!["example.py".to_string(), "clean.py".to_string()];
+    let xml = to_junit(&diagnostics, &files);
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    assert_eq!(xml.matches("<failure ").count(), diagnostics.len());
+    assert_eq!(xml.matches("<testcase ").count(), 3);
+    assert!(xml.contains(&format!("failures=\"{}\"", diagnostics.len())));
+    assert!(xml.contains("<testcase name=\"clean.py\" classname=\"clean.py\" file=\"clean.py\"/>"));
+    assert!(xml.contains("Comparison &apos;a &lt; b&apos; uses &lt;&quot;quotes&quot;&gt; &amp; more"));
+    assert!(!xml.contains("'a < b'"));
+}
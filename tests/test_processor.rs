@@ -1,6 +1,10 @@
 use anyhow::Result;
-use rustlint::processor::{process_files, find_python_files};
-use rustlint::rules::{get_default_rules, Rule};
+use rustlint::config::Paths;
+use rustlint::linter::{Diagnostic, DiagnosticLevel, LintContext};
+use rustlint::output::ProgressBar;
+use rustlint::processor::{process_files, process_files_with_options, find_python_files, find_python_files_with_config, thread_pool, ProcessOptions};
+use std::sync::{Arc, Mutex};
+use rustlint::rules::{Rule, RuleRegistry};
 use std::fs::{self, File};
 use std::io::Write;
 use tempfile::tempdir;
@@ -43,10 +47,7 @@ fn test_find_python_files() -> Result<()> {
 #[test]
 fn test_parallel_processing() -> Result<()> {
     let dir = tempdir()?;
-    let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
-        .into_iter()
-        .map(|r| r as Box<dyn Rule + Sync>)
-        .collect();
+    let rules = RuleRegistry::with_defaults();
     
     // Create test files with various issues
     let files = vec![
@@ -95,10 +96,7 @@ fn test_parallel_processing() -> Result<()> {
 #[test]
 fn test_parallel_processing_large_files() -> Result<()> {
     let dir = tempdir()?;
-    let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
-        .into_iter()
-        .map(|r| r as Box<dyn Rule + Sync>)
-        .collect();
+    let rules = RuleRegistry::with_defaults();
     
     // Create 10 large files with deliberate issues
     let mut file_paths = Vec::new();
@@ -153,11 +151,339 @@ fn test_empty_directory() -> Result<()> {
 
 #[test]
 fn test_process_empty_file_list() -> Result<()> {
-    let rules: Vec<Box<dyn Rule + Sync>> = get_default_rules()
-        .into_iter()
-        .map(|r| r as Box<dyn Rule + Sync>)
-        .collect();
+    let rules = RuleRegistry::with_defaults();
     let diagnostics = process_files(Vec::new(), &rules)?;
     assert!(diagnostics.is_empty());
     Ok(())
 }
+
+#[test]
+fn test_find_python_files_respects_gitignore() -> Result<()> {
+    let dir = tempdir()?;
+
+    let files = vec![
+        (".gitignore", "ignored/\n"),
+        ("kept.py", "x = 1"),
+        ("ignored/skipped.py", "y = 2"),
+        ("pkg/.ignore", "generated_*.py\n"),
+        ("pkg/module.py", "z = 3"),
+        ("pkg/generated_api.py", "w = 4"),
+    ];
+    for (path, content) in files {
+        let full_path = dir.path().join(path);
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(full_path, content)?;
+    }
+
+    let mut python_files = find_python_files(&dir.path().to_path_buf())?;
+    python_files.sort();
+
+    let expected = vec![dir.path().join("kept.py"), dir.path().join("pkg/module.py")];
+    assert_eq!(python_files, expected);
+    Ok(())
+}
+
+#[test]
+fn test_find_python_files_applies_exclude_globs() -> Result<()> {
+    let dir = tempdir()?;
+    for path in ["main.py", "venv/lib/site.py", "build/out.py"] {
+        let full_path = dir.path().join(path);
+        fs::create_dir_all(full_path.parent().unwrap())?;
+        fs::write(full_path, "x = 1")?;
+    }
+
+    let paths = Paths {
+        exclude: vec!["venv/*".to_string(), "build/*".to_string()],
+        ..Paths::default()
+    };
+    let python_files = find_python_files_with_config(&dir.path().to_path_buf(), &paths)?;
+
+    assert_eq!(python_files, vec![dir.path().join("main.py")]);
+    Ok(())
+}
+
+#[test]
+fn test_diagnostics_sorted_by_path_and_line() -> Result<()> {
+    let dir = tempdir()?;
+    let names = ["c.py", "a.py", "b.py"];
+    let mut files = Vec::new();
+    for name in names {
+        let path = dir.path().join(name);
+        fs::write(&path, "import os\nx = 1   \nimport sys\n")?;
+        files.push(path);
+    }
+
+    let rules = RuleRegistry::with_defaults();
+    let mut diagnostics = process_files(files, &rules)?;
+    rustlint::output::sort_diagnostics(&mut diagnostics);
+
+    let keys: Vec<_> = diagnostics.iter().map(|d| (d.path.clone(), d.line, d.column)).collect();
+    let mut expected = keys.clone();
+    expected.sort();
+    assert_eq!(keys, expected);
+
+    // Each file contributes diagnostics on lines 1, 2 and 3, in file order
+    let first_file: Vec<_> = diagnostics.iter().take_while(|d| d.path.ends_with("a.py")).map(|d| d.line).collect();
+    assert_eq!(first_file, vec![1, 2, 3]);
+    assert!(diagnostics.last().unwrap().path.ends_with("c.py"));
+
+    Ok(())
+}
+
+#[test]
+fn test_single_job_matches_parallel() -> Result<()> {
+    let dir = tempdir()?;
+    let mut files = Vec::new();
+    for i in 0..8 {
+        let path = dir.path().join(format!("module_{}.py", i));
+        fs::write(&path, format!("import os\nimport sys\nvalue_{} = 1   \n", i))?;
+        files.push(path);
+    }
+
+    let rules = RuleRegistry::with_defaults();
+
+    let summarize = |diagnostics: Vec<rustlint::linter::Diagnostic>| {
+        let mut keys: Vec<_> = diagnostics.into_iter().map(|d| (d.path, d.line, d.column, d.code)).collect();
+        keys.sort();
+        keys
+    };
+
+    let parallel = summarize(thread_pool(0)?.install(|| process_files(files.clone(), &rules))?);
+    let single = summarize(thread_pool(1)?.install(|| process_files(files.clone(), &rules))?);
+    assert!(!parallel.is_empty());
+    assert_eq!(parallel, single);
+
+    Ok(())
+}
+
+/// Collects everything the progress bar draws.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_progress_matches_plain_run() -> Result<()> {
+    let dir = tempdir()?;
+    let mut files = Vec::new();
+    for i in 0..8 {
+        let path = dir.path().join(format!("module_{}.py", i));
+        fs::write(&path, format!("import os\nvalue_{} = 1   \n", i))?;
+        files.push(path);
+    }
+
+    let rules = RuleRegistry::with_defaults();
+    let summarize = |diagnostics: Vec<Diagnostic>| {
+        let mut keys: Vec<_> = diagnostics.into_iter().map(|d| (d.path, d.line, d.column, d.code)).collect();
+        keys.sort();
+        keys
+    };
+
+    let buffer = SharedBuffer::default();
+    let progress = ProgressBar::with_writer(files.len(), Box::new(buffer.clone()));
+    let options = ProcessOptions { progress: Some(&progress), ..ProcessOptions::default() };
+    let with_progress = summarize(thread_pool(4)?.install(|| process_files_with_options(files.clone(), &rules, &options))?);
+    let plain = summarize(process_files(files.clone(), &rules)?);
+
+    assert!(!plain.is_empty());
+    assert_eq!(with_progress, plain);
+    assert_eq!(progress.position(), 8);
+    let drawn = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+    assert!(drawn.contains("8/8 files"), "{}", drawn);
+
+    Ok(())
+}
+
+#[test]
+fn test_excluded_files_are_not_linted() -> Result<()> {
+    let dir = tempdir()?;
+    for path in ["app.py", "generated/models.py", "generated/deep/schema.py", "app/migrations/0001_initial.py", "app/views.py"] {
+        let full_path = dir.path().join(path);
+        fs::create_dir_all(full_path.parent().unwrap())?;
+        fs::write(full_path, "import os\n")?;
+    }
+
+    let paths = Paths {
+        exclude: vec!["generated/*".to_string(), "**/migrations/*.py".to_string()],
+        ..Paths::default()
+    };
+    let files = find_python_files_with_config(&dir.path().to_path_buf(), &paths)?;
+
+    let rules = RuleRegistry::with_defaults();
+    let diagnostics = process_files(files, &rules)?;
+
+    let mut linted: Vec<_> = diagnostics
+        .iter()
+        .map(|d| std::path::Path::new(&d.path).strip_prefix(dir.path()).unwrap().to_path_buf())
+        .collect();
+    linted.sort();
+    linted.dedup();
+    assert_eq!(linted, vec![std::path::PathBuf::from("app/views.py"), std::path::PathBuf::from("app.py")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_find_stub_files_with_extension() -> Result<()> {
+    let dir = tempdir()?;
+    fs::write(dir.path().join("module.py"), "x = 1\n")?;
+    fs::write(dir.path().join("module.pyi"), "import os\n\ndef f(x: int) -> int: ...\n")?;
+
+    let python_files = find_python_files(&dir.path().to_path_buf())?;
+    assert_eq!(python_files, vec![dir.path().join("module.py")]);
+
+    let mut paths = Paths::default();
+    paths.extensions.push("pyi".to_string());
+    let mut files = find_python_files_with_config(&dir.path().to_path_buf(), &paths)?;
+    files.sort();
+    assert_eq!(files, vec![dir.path().join("module.py"), dir.path().join("module.pyi")]);
+
+    let rules = RuleRegistry::with_defaults();
+    let diagnostics = process_files(files, &rules)?;
+    let unused: Vec<_> = diagnostics.iter().filter(|d| d.code == "F401").collect();
+    assert_eq!(unused.len(), 1);
+    assert!(unused[0].path.ends_with("module.pyi"));
+    assert!(!diagnostics.iter().any(|d| d.code == "E999"));
+
+    Ok(())
+}
+
+/// A downstream rule that flags every `TODO` comment.
+struct NoTodo;
+
+impl Rule for NoTodo {
+    fn code(&self) -> &'static str {
+        "X100"
+    }
+
+    fn name(&self) -> &'static str {
+        "no-todo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flags TODO comments."
+    }
+
+    fn check(&self, context: &LintContext) -> Result<Vec<Diagnostic>> {
+        Ok(context
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let column = line.find("# TODO")? + 1;
+                Some(Diagnostic {
+                    level: DiagnosticLevel::Warning,
+                    message: "TODO comment".to_string(),
+                    line: i + 1,
+                    column,
+                    end_line: i + 1,
+                    end_column: line.len(),
+                    path: String::new(),
+                    code: "X100".to_string(),
+                })
+            })
+            .collect())
+    }
+}
+
+#[test]
+fn test_custom_rule_runs_alongside_builtins() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("module.py");
+    fs::write(&file, "import os
+x = 1  # TODO: remove
+")?;
+
+    let mut rules = RuleRegistry::with_defaults();
+    let builtin_count = rules.len();
+    rules.register(Box::new(NoTodo));
+    assert_eq!(rules.len(), builtin_count + 1);
+
+    let diagnostics = process_files(vec![file], &rules)?;
+    let todo: Vec<_> = diagnostics.iter().filter(|d| d.code == "X100").collect();
+    assert_eq!(todo.len(), 1);
+    assert_eq!((todo[0].line, todo[0].column), (2, 8));
+    assert!(diagnostics.iter().any(|d| d.code == "F401"));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_find_python_files_follows_symlinks_when_enabled() -> Result<()> {
+    let dir = tempdir()?;
+    let outside = tempdir()?;
+    fs::write(dir.path().join("main.py"), "x = 1")?;
+    fs::write(outside.path().join("linked.py"), "x = 1")?;
+    std::os::unix::fs::symlink(outside.path(), dir.path().join("vendor"))?;
+    // A cycle back to the root must not recurse forever
+    std::os::unix::fs::symlink(dir.path(), dir.path().join("loop"))?;
+
+    let python_files = find_python_files(&dir.path().to_path_buf())?;
+    assert_eq!(python_files, vec![dir.path().join("main.py")]);
+
+    let paths = Paths { follow_symlinks: true, ..Paths::default() };
+    let mut python_files = find_python_files_with_config(&dir.path().to_path_buf(), &paths)?;
+    python_files.sort();
+    assert_eq!(python_files, vec![dir.path().join("main.py"), dir.path().join("vendor/linked.py")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_rules_enabled_by_config() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("toggled.py");
+    fs::write(&file, "import os\nprint('hi')\n")?;
+
+    let codes = |config: &rustlint::config::Config| -> Result<Vec<String>> {
+        let rules = RuleRegistry::from_config(config);
+        Ok(process_files(vec![file.clone()], &rules)?.into_iter().map(|d| d.code).collect())
+    };
+
+    let config = rustlint::config::Config::default();
+    assert_eq!(codes(&config)?, vec!["F401"]);
+
+    let config: rustlint::config::Config = toml::from_str("[rules.enabled]\nF401 = false\nT201 = true\n")?;
+    assert_eq!(codes(&config)?, vec!["T201"]);
+
+    // An explicit entry wins over the older per-rule switch
+    let config: rustlint::config::Config = toml::from_str("[rules]\nwarn_print = true\n\n[rules.enabled]\nT201 = false\n")?;
+    assert_eq!(codes(&config)?, vec!["F401"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_indentation_checked_when_processing_files() -> Result<()> {
+    let dir = tempdir()?;
+    let file = dir.path().join("three.py");
+    fs::write(&file, "def f():\n   return 1\n")?;
+
+    let lint = |config: &rustlint::config::Config| -> Result<Vec<(String, String)>> {
+        let rules = RuleRegistry::from_config(config);
+        Ok(process_files(vec![file.clone()], &rules)?.into_iter().map(|d| (d.code, d.message)).collect())
+    };
+
+    let config = rustlint::config::Config::default();
+    assert_eq!(lint(&config)?, vec![("E111".to_string(), "Indentation of 3 spaces should be a multiple of 4".to_string())]);
+
+    let config: rustlint::config::Config = toml::from_str("[rules]\nindent_size = 3\n")?;
+    assert!(lint(&config)?.is_empty());
+
+    let config: rustlint::config::Config = toml::from_str("[rules]\nuse_tabs = true\n")?;
+    assert_eq!(lint(&config)?, vec![("W191".to_string(), "Indentation contains spaces (use tabs instead)".to_string())]);
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
-use rustlint::rules::{UnusedImports, LineLength, NamingConventions, Rule};
-use rustlint::linter::DiagnosticLevel;
+use rustlint::rules::{filter_rules, get_default_rules, list_rules, UnusedImports, LineLength, NamingConventions, NoneComparison, StarImport, FStringMissingPlaceholders, Complexity, BuiltinShadowing, UnusedVariables, LambdaAssignment, BoolComparison, BlankLinesBeforeDef, BlankLinesBeforeMethod, DuplicateDictKey, FileEncoding, FinalNewline, LineEndings, AssertOnTuple, MultipleImports, ImportOrder, PrintStatement, Redefinition, LenComparison, StringConcatInLoop, ConstantComparison, TrailingWhitespace, FirstArgSelf, EmptyBody, MissingDocstring, ExceptOrder, RaiseWithoutFrom, FunctionLength, MisspelledDunder, RedundantPass, InconsistentReturns, MissingTypeAnnotations, AttributeOutsideInit, SilentExcept, IsLiteral, LoopVariableShadowing, FStringNestedQuotes, SuspiciousAssignTarget, NotInNotEq, OpenWithoutWith, DuplicateDefinition, IncompatibleComparison, ReturnInFinally, AmbiguousName, ImplicitNamespacePackage, TrailingComma, FloatEquality, UnusedExceptionVariable, TrailingBlankLines, Rule};
+use rustlint::linter::LintContext;
 use std::path::PathBuf;
 use std::fs;
 
@@ -25,8 +25,8 @@ def main():
     return my_list
 "#;
     let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
-    let rule = UnusedImports;
-    let diagnostics = rule.check(&ast, code)?;
+    let rule = UnusedImports::default();
+    let diagnostics = rule.check(&LintContext::new(code, &ast))?;
 
     assert_eq!(diagnostics.len(), 4);
     assert!(diagnostics.iter().any(|d| d.message.contains("os")));
@@ -51,7 +51,7 @@ And finally we have a very long line that should definitely trigger the line len
 "#;
     let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
     let rule = LineLength::new(88);
-    let diagnostics = rule.check(&ast, code)?;
+    let diagnostics = rule.check(&LintContext::new(code, &ast))?;
 
     assert_eq!(diagnostics.len(), 3);
     assert_eq!(diagnostics[0].line, 2); // Comment line
@@ -80,7 +80,7 @@ def good_name():
 "#;
     let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
     let rule = NamingConventions;
-    let diagnostics = rule.check(&ast, code)?;
+    let diagnostics = rule.check(&LintContext::new(code, &ast))?;
 
     assert_eq!(diagnostics.len(), 3);
     assert!(diagnostics.iter().any(|d| d.message.contains("badFunction")));
@@ -93,7 +93,7 @@ def good_name():
 #[test]
 fn test_multiple_rules() -> Result<()> {
     let rules: Vec<Box<dyn Rule>> = vec![
-        Box::new(UnusedImports),
+        Box::new(UnusedImports::default()),
         Box::new(LineLength::new(88)),
         Box::new(NamingConventions),
     ];
@@ -107,7 +107,7 @@ fn test_multiple_rules() -> Result<()> {
 
         let mut all_diagnostics = Vec::new();
         for rule in &rules {
-            let diagnostics = rule.check(&ast, &content)?;
+            let diagnostics = rule.check(&LintContext::new(&content, &ast))?;
             all_diagnostics.extend(diagnostics);
         }
 
@@ -120,3 +120,1790 @@ fn test_multiple_rules() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_filter_rules() {
+    let codes = |rules: &[Box<dyn Rule>]| rules.iter().map(|r| r.code()).collect::<Vec<_>>();
+    let select = vec!["E501".to_string()];
+    let ignore = vec!["F401".to_string()];
+
+    let all_codes = codes(&get_default_rules());
+
+    // No filters keep every default rule
+    let rules = filter_rules(get_default_rules(), &[], &[]);
+    assert_eq!(codes(&rules), all_codes);
+
+    // --select is an allowlist
+    let rules = filter_rules(get_default_rules(), &select, &[]);
+    assert_eq!(codes(&rules), vec!["E501"]);
+
+    // --ignore is a denylist
+    let rules = filter_rules(get_default_rules(), &[], &ignore);
+    let expected: Vec<_> = all_codes.iter().copied().filter(|code| *code != "F401").collect();
+    assert_eq!(codes(&rules), expected);
+
+    // --ignore wins when a code is both selected and ignored
+    let both = vec!["E501".to_string(), "F401".to_string()];
+    let rules = filter_rules(get_default_rules(), &both, &ignore);
+    assert_eq!(codes(&rules), vec!["E501"]);
+}
+
+#[test]
+fn test_none_comparison() -> Result<()> {
+    let code = r#"
+if x == None:
+    pass
+if x is None:
+    pass
+if x != None:
+    pass
+if a == None == b:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let rule = NoneComparison;
+    let diagnostics = rule.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 4);
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].message, "Use 'is None' instead of '== None'");
+    assert_eq!(diagnostics[0].column, 9);
+    assert_eq!(diagnostics[1].line, 6);
+    assert_eq!(diagnostics[1].message, "Use 'is not None' instead of '!= None'");
+    // Each operator touching None in a chained comparison is reported
+    assert_eq!(diagnostics[2].line, 8);
+    assert_eq!(diagnostics[2].column, 9);
+    assert_eq!(diagnostics[3].line, 8);
+    assert_eq!(diagnostics[3].column, 17);
+    Ok(())
+}
+
+#[test]
+fn test_star_import() -> Result<()> {
+    let code = "from os import *\nfrom os import path\nprint(path)\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = StarImport.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "from 'os' import * used; import only the names you need"
+    );
+
+    // The unused-imports rule leaves the wildcard to StarImport
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+    assert!(diagnostics.is_empty(), "Unexpected diagnostics: {:?}", diagnostics);
+    Ok(())
+}
+
+#[test]
+fn test_fstring_missing_placeholders() -> Result<()> {
+    let code = r#"
+greeting = f"hello"
+message = f"hi {name}"
+padded = f"{name:>10}"
+plain = "hello"
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = FStringMissingPlaceholders.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].column, 12);
+    assert_eq!(diagnostics[0].message, "f-string without any placeholders");
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_in_comprehensions_and_fstrings() -> Result<()> {
+    let code = r#"
+import os
+import json
+import re
+import sys
+import math
+import unused
+
+paths = [os.path.join(p) for p in sys.argv]
+message = f"{json.dumps(paths)}"
+matches = {name: re.match(name, "x") for name in paths if name}
+values = (math.floor(v) for v in [1.5, 2.5])
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1, "Unexpected diagnostics: {:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("unused"));
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_in_collection_literals() -> Result<()> {
+    let code = r#"
+import os
+import sys
+import json
+from typing import List
+
+items = (os.sep, [sys.path], {json.loads("1")}, {"key": List})
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+
+    assert!(diagnostics.is_empty(), "Unexpected diagnostics: {:?}", diagnostics);
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_in_nested_blocks() -> Result<()> {
+    let code = r#"
+import io
+import logging
+import json
+import sys
+import unused
+
+class Loader:
+    def load(self, path):
+        with io.open(path) as f:
+            try:
+                return json.load(f)
+            except ValueError:
+                logging.warning("bad file")
+            finally:
+                if sys.flags.debug:
+                    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1, "Unexpected diagnostics: {:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("unused"));
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_in_with_and_except() -> Result<()> {
+    let code = r#"
+import contextlib
+import traceback
+
+with contextlib.suppress(KeyError) as f:
+    pass
+
+try:
+    run()
+except Exception:
+    traceback.print_exc()
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+
+    assert!(diagnostics.is_empty(), "Unexpected diagnostics: {:?}", diagnostics);
+    Ok(())
+}
+
+#[test]
+fn test_complexity() -> Result<()> {
+    let code = r#"
+def process(items, flag, mode):
+    for item in items:
+        if item and flag:
+            continue
+        elif item or mode:
+            pass
+        while mode:
+            mode -= 1
+    try:
+        run()
+    except ValueError:
+        pass
+    except KeyError:
+        pass
+    value = 1 if flag else 2
+    evens = [i for i in items if i % 2 if i > 0]
+    return value
+
+def simple(x):
+    if x:
+        return 1
+    return 0
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = Complexity::new(10).check(&LintContext::new(code, &ast))?;
+
+    // 1 + for + if + and + elif + or + while + 2 handlers + ifexp + 2 comprehension ifs = 12
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].message, "Function 'process' is too complex (12 > 10)");
+
+    // The simple function only trips a very low threshold
+    let diagnostics = Complexity::new(1).check(&LintContext::new(code, &ast))?;
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().any(|d| d.message == "Function 'simple' is too complex (2 > 1)"));
+    Ok(())
+}
+
+#[test]
+fn test_builtin_shadowing() -> Result<()> {
+    let code = r#"
+list = [1, 2]
+def id():
+    pass
+def lookup(type, key):
+    return key
+obj.type = 1
+items = [1]
+
+class Model:
+    id = 1
+    def format(self):
+        pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = BuiltinShadowing.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(
+        messages,
+        vec![
+            (2, "Redefinition of builtin 'list'"),
+            (3, "Redefinition of builtin 'id'"),
+            (5, "Redefinition of builtin 'type'"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unused_variables() -> Result<()> {
+    let code = r#"
+def compute(values):
+    unused = 10
+    _ = len(values)
+    _scratch = 5
+    total = sum(values)
+    count = 0
+    count += 1
+    return total
+
+def outer():
+    captured = 1
+    def inner():
+        return captured
+    return inner
+
+module_level = 1
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedVariables.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1, "Unexpected diagnostics: {:?}", diagnostics);
+    assert_eq!(diagnostics[0].line, 3);
+    assert_eq!(diagnostics[0].message, "Local variable 'unused' is assigned to but never used");
+    Ok(())
+}
+
+#[test]
+fn test_unused_variables_respects_config() {
+    let mut config = rustlint::config::Config::default();
+    let has_rule = |config: &rustlint::config::Config| {
+        rustlint::rules::get_rules(config).iter().any(|r| r.code() == "F841")
+    };
+    assert!(has_rule(&config));
+
+    config.rules.ignore_unused_variables = true;
+    assert!(!has_rule(&config));
+}
+
+#[test]
+fn test_line_length_ignore_long_strings() -> Result<()> {
+    let code = format!(
+        "def fetch():\n    \"\"\"Fetch the data.\n\n    See https://example.com/{}\n    \"\"\"\n    return 1\n",
+        "a".repeat(100)
+    );
+    let ast = rustpython_parser::parser::parse_program(&code, "<string>")?;
+
+    let diagnostics = LineLength::new(88).check(&LintContext::new(&code, &ast))?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 4);
+
+    let diagnostics = LineLength::new(88).with_ignore_long_strings(true).check(&LintContext::new(&code, &ast))?;
+    assert!(diagnostics.is_empty());
+
+    // A long line of code is still reported when its only string is short
+    let args = (0..30).map(|i| format!("arg{}", i)).collect::<Vec<_>>().join(", ");
+    let code = format!("result = call({}, \"x\")\nmessage = \"{}\"\n", args, "b".repeat(100));
+    let ast = rustpython_parser::parser::parse_program(&code, "<string>")?;
+    let diagnostics = LineLength::new(88).with_ignore_long_strings(true).check(&LintContext::new(&code, &ast))?;
+    let lines: Vec<_> = diagnostics.iter().map(|d| d.line).collect();
+    assert_eq!(lines, vec![1]);
+
+    Ok(())
+}
+
+#[test]
+fn test_rule_metadata_unique() {
+    let rules = list_rules();
+    assert_eq!(rules.len(), get_default_rules().len());
+
+    let mut codes = std::collections::HashSet::new();
+    let mut names = std::collections::HashSet::new();
+    for rule in &rules {
+        assert!(!rule.code.is_empty());
+        assert!(!rule.name.is_empty());
+        assert!(!rule.description.is_empty());
+        assert!(codes.insert(rule.code), "duplicate code {}", rule.code);
+        assert!(names.insert(rule.name), "duplicate name {}", rule.name);
+    }
+}
+
+#[test]
+fn test_lambda_assignment() -> Result<()> {
+    let code = "f = lambda: 1\ng: Callable = lambda x: x\nys = sorted(xs, key=lambda x: x)\nhandlers = {'a': lambda: 2}\nobj.attr = lambda: 3\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = LambdaAssignment.check(&LintContext::new(code, &ast))?;
+
+    let lines: Vec<_> = diagnostics.iter().map(|d| d.line).collect();
+    assert_eq!(lines, vec![1, 2]);
+    assert_eq!(diagnostics[0].message, "Do not assign a lambda expression, use a def");
+    assert_eq!(diagnostics[0].code, "E731");
+
+    Ok(())
+}
+
+#[test]
+fn test_bool_comparison() -> Result<()> {
+    let code = "if x == True:\n    pass\nif x is True:\n    pass\nif False != y:\n    pass\nif True == False:\n    pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = BoolComparison.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].line, 1);
+    assert_eq!(diagnostics[0].column, 9);
+    assert_eq!(diagnostics[0].message, "Comparison to True should be 'if cond:' or 'if cond is True:'");
+    assert_eq!(diagnostics[1].line, 5);
+    assert!(diagnostics[1].message.starts_with("Comparison to False"));
+
+    Ok(())
+}
+
+#[test]
+fn test_blank_lines_before_def() -> Result<()> {
+    let code = "import os\n\ndef one():\n    pass\n\n\n@decorator\ndef two():\n    pass\n\n\nclass Thing:\n    def a(self):\n        pass\n    def b(self):\n        pass\n\n    # comment\n    def c(self):\n        pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = BlankLinesBeforeDef.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].line, 3);
+    assert_eq!(diagnostics[0].code, "E302");
+    assert_eq!(diagnostics[0].message, "Expected 2 blank lines, found 1");
+
+    let diagnostics = BlankLinesBeforeMethod.check(&LintContext::new(code, &ast))?;
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].line, 15);
+    assert_eq!(diagnostics[0].code, "E301");
+    assert_eq!(diagnostics[0].message, "Expected 1 blank line, found 0");
+
+    // The first definition in a file needs no blank lines
+    let code = "def first():\n    pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(BlankLinesBeforeDef.check(&LintContext::new(code, &ast))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_too_many_blank_lines_before_def() -> Result<()> {
+    let code = "import os\n\n\n\ndef one():\n    pass\n\n\nclass Thing:\n    def a(self):\n        pass\n\n\n    def b(self):\n        pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let context = LintContext::new(code, &ast);
+
+    let diagnostics = BlankLinesBeforeDef.check(&context)?;
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(5, "Expected 2 blank lines, found 3")]);
+
+    let diagnostics = BlankLinesBeforeMethod.check(&context)?;
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(14, "Expected 1 blank line, found 2")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_dict_key() -> Result<()> {
+    let code = "a = {\"a\": 1, \"a\": 2}\nb = {1: \"x\", 1.0: \"y\"}\nc = {\"a\": 1, \"b\": 2, 1: 3, x: 4, x: 5}\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = DuplicateDictKey.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].message, "Duplicate key \"a\" in dict literal");
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 14));
+    assert_eq!(diagnostics[1].line, 2);
+    assert_eq!(diagnostics[1].message, "Duplicate key 1.0 in dict literal");
+
+    Ok(())
+}
+
+#[test]
+fn test_file_encoding() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bom.py");
+    fs::write(&path, b"\xef\xbb\xbfx = 1\n")?;
+    let rules: Vec<Box<dyn Rule + Sync>> = vec![Box::new(FileEncoding)];
+    let diagnostics = rustlint::linter::lint_file(&path, &rules)?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "File starts with a UTF-8 BOM");
+
+    let path = dir.path().join("latin.py");
+    fs::write(&path, b"#!/usr/bin/env python\n# -*- coding: latin-1 -*-\nname = '\xe9'\n")?;
+    let diagnostics = rustlint::linter::lint_file(&path, &rules)?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Non-utf-8 encoding declaration");
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 15));
+
+    let code = "# coding: utf-8\nx = 1\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(FileEncoding.check(&LintContext::new(code, &ast))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_final_newline() -> Result<()> {
+    let check = |code: &str| -> Result<Vec<rustlint::linter::Diagnostic>> {
+        let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+        FinalNewline.check(&LintContext::new(code, &ast))
+    };
+
+    let diagnostics = check("x = 1\ny = 2")?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "No newline at end of file");
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 6));
+
+    assert!(check("x = 1\n")?.is_empty());
+
+    assert!(check("x = 1\n\n\n")?.is_empty());
+    assert!(check("")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_trailing_blank_lines() -> Result<()> {
+    let check = |code: &str| -> Result<Vec<rustlint::linter::Diagnostic>> {
+        let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+        TrailingBlankLines.check(&LintContext::new(code, &ast))
+    };
+
+    let diagnostics = check("x = 1\n\n\n")?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "W391");
+    assert_eq!(diagnostics[0].message, "Blank lines at end of file");
+    assert_eq!(diagnostics[0].line, 2);
+
+    assert!(check("x = 1\n")?.is_empty());
+    assert!(check("x = 1")?.is_empty());
+    assert!(check("\n\n")?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_line_endings() -> Result<()> {
+    use rustlint::config::LineEnding;
+
+    let lf = "x = 1\ny = 2\n";
+    let crlf = "x = 1\r\ny = 2\r\n";
+    let mixed = "x = 1\r\ny = 2\nz = 3\r\n";
+
+    let check = |setting: LineEnding, code: &str| -> Result<Vec<String>> {
+        let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+        Ok(LineEndings::new(setting).check(&LintContext::new(code, &ast))?.into_iter().map(|d| d.message).collect())
+    };
+
+    assert!(check(LineEnding::Lf, lf)?.is_empty());
+    assert_eq!(check(LineEnding::Lf, crlf)?, vec!["Expected LF line endings, found CRLF"]);
+    assert_eq!(check(LineEnding::Lf, mixed)?, vec!["Expected LF line endings, found CRLF"]);
+
+    assert_eq!(check(LineEnding::Crlf, lf)?, vec!["Expected CRLF line endings, found LF"]);
+    assert!(check(LineEnding::Crlf, crlf)?.is_empty());
+    assert_eq!(check(LineEnding::Crlf, mixed)?, vec!["Expected CRLF line endings, found LF"]);
+
+    assert!(check(LineEnding::Auto, lf)?.is_empty());
+    assert!(check(LineEnding::Auto, crlf)?.is_empty());
+    assert_eq!(check(LineEnding::Auto, mixed)?, vec!["Mixed line endings (CRLF and LF)"]);
+
+    let ast = rustpython_parser::parser::parse_program(mixed, "<string>")?;
+    assert_eq!(LineEndings::new(LineEnding::Auto).check(&LintContext::new(mixed, &ast))?[0].line, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_diagnostic_spans() -> Result<()> {
+    let long_line = format!("x = '{}'", "a".repeat(100));
+    let code = format!("{}\n", long_line);
+    let ast = rustpython_parser::parser::parse_program(&code, "<string>")?;
+    let diagnostics = LineLength::new(88).check(&LintContext::new(&code, &ast))?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 89));
+    assert_eq!((diagnostics[0].end_line, diagnostics[0].end_column), (1, long_line.chars().count()));
+
+    // Unused imports span the imported name, including any alias
+    let code = "import os, sys as system\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_line, d.end_column)).collect();
+    assert_eq!(spans, vec![(1, 8, 1, 9), (1, 12, 1, 24)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_assert_on_tuple() -> Result<()> {
+    let code = "def check(x):\n    assert (x, \"msg\")\n    assert x, \"msg\"\n    assert (x)\n    assert ()\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = AssertOnTuple.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 2);
+    assert_eq!(diagnostics[0].message, "Assertion on a non-empty tuple is always true");
+
+    Ok(())
+}
+
+#[test]
+fn test_global_statement() -> Result<()> {
+    let code = "global ignored\n\ndef bump():\n    global counter, total\n    counter += 1\n    def inner():\n        nonlocal step\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+
+    let mut config = rustlint::config::Config::default();
+    let run = |config: &rustlint::config::Config| -> Result<Vec<String>> {
+        let mut messages = Vec::new();
+        for rule in rustlint::rules::get_rules(config) {
+            if rule.code() == "PLW0603" {
+                messages.extend(rule.check(&LintContext::new(code, &ast))?.into_iter().map(|d| d.message));
+            }
+        }
+        Ok(messages)
+    };
+
+    assert!(run(&config)?.is_empty());
+
+    config.rules.warn_global = true;
+    assert_eq!(run(&config)?, vec!["Use of 'global' for: counter, total", "Use of 'nonlocal' for: step"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_multiple_imports() -> Result<()> {
+    let code = "import os, sys\nfrom os import path, sep\nimport json\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = MultipleImports.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 1);
+    assert_eq!(diagnostics[0].message, "Multiple imports on one line; put each on its own line");
+
+    Ok(())
+}
+
+#[test]
+fn test_import_order() -> Result<()> {
+    let sorted = "from __future__ import annotations\n\nimport os\nimport sys\n# typing helpers\nfrom collections import OrderedDict\nfrom typing import List\n\nimport requests\nfrom yaml import safe_load\n\nfrom . import models\nfrom .utils import helper\n";
+    let ast = rustpython_parser::parser::parse_program(sorted, "<string>")?;
+    assert!(ImportOrder.check(&LintContext::new(sorted, &ast))?.is_empty());
+
+    let unsorted = "import sys\nimport requests\n# stdlib should come first\nimport os\n";
+    let ast = rustpython_parser::parser::parse_program(unsorted, "<string>")?;
+    let diagnostics = ImportOrder.check(&LintContext::new(unsorted, &ast))?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, 4);
+    assert_eq!(diagnostics[0].message, "Imports are not sorted");
+
+    Ok(())
+}
+
+#[test]
+fn test_print_statement() -> Result<()> {
+    let code = "print(\"x\")\nobj.print()\ndef f():\n    return print\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = PrintStatement.check(&LintContext::new(code, &ast))?;
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 1));
+    assert_eq!(diagnostics[0].code, "T201");
+
+    // Opt-in only
+    let mut config = rustlint::config::Config::default();
+    assert!(!rustlint::rules::get_rules(&config).iter().any(|r| r.code() == "T201"));
+    config.rules.warn_print = true;
+    assert!(rustlint::rules::get_rules(&config).iter().any(|r| r.code() == "T201"));
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_relative() -> Result<()> {
+    let code = "from . import utils\nfrom . import models as m\nfrom .pkg import thing\nfrom ..shared import helper\n\nutils.run()\nhelper()\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, vec!["Unused import 'models as m'", "Unused import 'thing'"]);
+    assert_eq!(diagnostics[0].line, 2);
+
+    let code = "from . import utils\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Unused import 'utils'");
+
+    Ok(())
+}
+
+#[test]
+fn test_redefinition() -> Result<()> {
+    let code = "import os\n\nimport json\nimport json\n\ndef f():\n    x = 1\n    x = x + 1\n    y = 1\n    y = 2\n    return x, y\n\nif os.name:\n    mode = 1\nelse:\n    mode = 2\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = Redefinition.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (4, "Redefinition of unused 'json' from line 3"),
+        (10, "Redefinition of unused 'y' from line 9"),
+    ]);
+
+    // A variable that is read before being reassigned is fine
+    let code = "x = 1\nprint(x)\nx = 2\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(Redefinition.check(&LintContext::new(code, &ast))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_dunder_all() -> Result<()> {
+    let code = "from x import foo\n\n__all__ = [\"foo\"]\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(UnusedImports::default().check(&LintContext::new(code, &ast))?.is_empty());
+
+    let code = "from x import foo, bar, baz\n\n__all__ = (\"foo\",)\n__all__ += [\"bar\"]\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedImports::default().check(&LintContext::new(code, &ast))?;
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "Unused import 'baz'");
+
+    Ok(())
+}
+
+#[test]
+fn test_len_comparison() -> Result<()> {
+    let code = "if len(items) == 0:\n    pass\nif len(items) > 0:\n    pass\nif len(a) == len(b):\n    pass\nif len(items) == 3:\n    pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = LenComparison.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (1, 4, 18, "Use 'not items' instead of 'len(items) == 0'"),
+        (3, 4, 17, "Use 'items' instead of 'len(items) > 0'"),
+    ]);
+
+    // A module that rebinds `len` isn't calling the builtin
+    let code = "from sizes import len\n\nif len(items) == 0:\n    pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(LenComparison.check(&LintContext::new(code, &ast))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_string_concat_in_loop() -> Result<()> {
+    let code = r#"
+def render(lines, numbers):
+    result = ""
+    total = 0
+    header: str = "-"
+    for line in lines:
+        result += line
+        total += 1
+    while numbers:
+        header = header + numbers.pop()
+    return result, total, header
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = StringConcatInLoop.check(&LintContext::new(code, &ast))?;
+
+    let lines: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column)).collect();
+    assert_eq!(lines, vec![(7, 9), (10, 9)]);
+    assert_eq!(diagnostics[0].message, "String concatenation in a loop; consider ''.join()");
+
+    // Numeric accumulators and strings built outside a loop are fine
+    let code = "total = 0\nfor n in numbers:\n    total += n\nname = \"a\"\nname += \"b\"\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(StringConcatInLoop.check(&LintContext::new(code, &ast))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_constant_comparison() -> Result<()> {
+    let code = "if 1 == 1:\n    pass\nif 2 > 3:\n    pass\nif \"a\" < \"b\" < \"c\":\n    pass\nif x == 1:\n    pass\nif f() == 1:\n    pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = ConstantComparison.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (1, 4, "Comparison '1 == 1' is always True"),
+        (3, 4, "Comparison '2 > 3' is always False"),
+        (5, 4, "Comparison '\"a\" < \"b\" < \"c\"' is always True"),
+    ]);
+
+    // Integers beyond 2^53 are compared exactly, not as rounded floats
+    let code = "a = 9007199254740993 == 9007199254740992\nb = 9007199254740993 > 9007199254740992.0\nc = 1 == 1.0\nd = -1 < -0.5\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = ConstantComparison.check(&LintContext::new(code, &ast))?;
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages, vec![
+        "Comparison '9007199254740993 == 9007199254740992' is always False",
+        "Comparison '9007199254740993 > 9007199254740992.0' is always True",
+        "Comparison '1 == 1.0' is always True",
+        "Comparison '-1 < -0.5' is always True",
+    ]);
+
+    // Non-ASCII text earlier on the line doesn't shift the quoted source
+    let code = "label = \"café\" if 2 > 3 else \"thé\"\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = ConstantComparison.check(&LintContext::new(code, &ast))?;
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(19, "Comparison '2 > 3' is always False")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_trailing_whitespace_inside_strings() -> Result<()> {
+    let code = "def f():\n    \"\"\"Markdown line break:  \n    next line.\n    \"\"\"   \n    return 1  \n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = TrailingWhitespace.check(&LintContext::new(code, &ast))?;
+
+    // Only the closing quotes' line and the code line, not the docstring's content
+    let lines: Vec<_> = diagnostics.iter().map(|d| d.line).collect();
+    assert_eq!(lines, vec![4, 5]);
+
+    Ok(())
+}
+
+#[test]
+fn test_first_arg_self() -> Result<()> {
+    let code = r#"
+class Greeter:
+    def greet(this, name):
+        def helper(value):
+            return value
+        return helper(name)
+
+    @staticmethod
+    def create(name):
+        return Greeter()
+
+    @classmethod
+    def default(cls):
+        return cls()
+
+    @classmethod
+    def other(self):
+        return self()
+
+    def __new__(cls):
+        return super().__new__(cls)
+
+    def ok(self):
+        pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = FirstArgSelf.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (3, 15, "First argument of method 'greet' should be 'self'"),
+        (17, 15, "First argument of method 'other' should be 'cls'"),
+    ]);
+
+    // Methods of a metaclass take the class they create
+    let code = "class Meta(type):\n    def __call__(cls, *args):\n        return super().__call__(*args)\n\n    def register(self):\n        pass\n\nclass Registry(abc.ABCMeta):\n    def __instancecheck__(cls, instance):\n        return True\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = FirstArgSelf.check(&LintContext::new(code, &ast))?;
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(5, "First argument of method 'register' should be 'cls'")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_body() -> Result<()> {
+    let code = r#"
+from abc import ABC, abstractmethod
+from typing import Protocol
+
+
+def f(): pass
+
+
+def todo():
+    ...
+
+
+def documented():
+    """Intentionally does nothing."""
+
+
+class Base:
+    @abstractmethod
+    def run(self):
+        pass
+
+
+class Shape(ABC):
+    def area(self):
+        ...
+
+
+class Readable(Protocol):
+    def read(self) -> bytes: ...
+
+
+class FooError(Exception): pass
+
+
+class ConfigError(errors.BaseError):
+    ...
+
+
+class DeprecatedWarning(UserWarning):
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = EmptyBody.check(&LintContext::new(code, &ast))?;
+
+    let lines: Vec<_> = diagnostics.iter().map(|d| d.line).collect();
+    assert_eq!(lines, vec![6, 9]);
+    assert_eq!(diagnostics[0].message, "Empty body; add an implementation or a docstring");
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_docstring() -> Result<()> {
+    let code = r#""""Helpers for processing orders."""
+
+
+def process(order):
+    def step():
+        pass
+    return step()
+
+
+def documented(order):
+    """Return the order unchanged."""
+    return order
+
+
+def _private():
+    pass
+
+
+class Order:
+    """An order."""
+
+    def __init__(self):
+        pass
+
+    def total(self):
+        return 0
+
+
+if TYPE_CHECKING:
+    def typed(order):
+        return order
+else:
+    try:
+        from fast import speedy
+    except ImportError:
+        def speedy(order):
+            return order
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = MissingDocstring.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (4, "Missing docstring for public function 'process'"),
+        (25, "Missing docstring for public method 'total'"),
+        (30, "Missing docstring for public function 'typed'"),
+        (36, "Missing docstring for public function 'speedy'"),
+    ]);
+
+    let code = "import os\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert_eq!(MissingDocstring.check(&LintContext::new(code, &ast))?[0].message, "Missing docstring for public module");
+
+    // Opt-in only
+    let mut config = rustlint::config::Config::default();
+    assert!(!rustlint::rules::get_rules(&config).iter().any(|r| r.code() == "D100"));
+    config.rules.require_docstrings = true;
+    assert!(rustlint::rules::get_rules(&config).iter().any(|r| r.code() == "D100"));
+
+    Ok(())
+}
+
+#[test]
+fn test_except_order() -> Result<()> {
+    let code = r#"
+try:
+    run()
+except Exception:
+    pass
+except ValueError:
+    pass
+
+try:
+    run()
+except (LookupError, OSError):
+    pass
+except (KeyError, MyError):
+    pass
+except IOError:
+    pass
+
+try:
+    run()
+except ValueError:
+    pass
+except Exception:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = ExceptOrder.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (6, 8, "Except handler 'ValueError' is unreachable; 'Exception' catches it first"),
+        (13, 9, "Except handler 'KeyError' is unreachable; 'LookupError' catches it first"),
+        (15, 8, "Except handler 'IOError' is unreachable; 'OSError' catches it first"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_raise_without_from() -> Result<()> {
+    let code = r#"
+try:
+    load()
+except KeyError as err:
+    raise ValueError("missing key")
+except OSError as err:
+    raise RuntimeError("io") from err
+except TypeError as err:
+    raise err
+except Exception:
+    raise
+
+raise ValueError("outside")
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = RaiseWithoutFrom.check(&LintContext::new(code, &ast))?;
+
+    let lines: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column)).collect();
+    assert_eq!(lines, vec![(5, 5)]);
+    assert_eq!(diagnostics[0].message, "Consider 'raise ... from err' to preserve the exception chain");
+
+    Ok(())
+}
+
+#[test]
+fn test_function_length() -> Result<()> {
+    let long_body = "    x = 1\n".repeat(4);
+    let code = format!(
+        "def handler(request):\n{}    if x:\n        for i in range(3):\n            x += i\n    return x\n\n\ndef short():\n    return 1\n",
+        long_body
+    );
+    let ast = rustpython_parser::parser::parse_program(&code, "<string>")?;
+    let diagnostics = FunctionLength::new(5).check(&LintContext::new(&code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(1, "Function 'handler' is too long (8 > 5 lines)")]);
+
+    assert!(FunctionLength::new(50).check(&LintContext::new(&code, &ast))?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_naming_convention_columns() -> Result<()> {
+    let code = "def badFunction():\n    pass\n\n\nclass   lower_class:\n    pass\n\n\ndef fetch_data():\n    x, camelCase = 1, 2\n    total = camelCase = 3\n\n\ndef classifyThis():\n    pass\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = NamingConventions.check(&LintContext::new(code, &ast))?;
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(1, 5, 15), (5, 9, 19), (11, 13, 21), (14, 5, 16)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_misspelled_dunder() -> Result<()> {
+    let code = r#"
+class Point:
+    def __init_(self):
+        pass
+
+    def _repr__(self):
+        pass
+
+    def __str(self):
+        pass
+
+    def __init__(self):
+        pass
+
+    def helper(self):
+        pass
+
+    def _len(self):
+        pass
+
+
+def __init_():
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = MisspelledDunder.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (3, "Method '__init_' looks like a misspelled dunder method"),
+        (6, "Method '_repr__' looks like a misspelled dunder method"),
+        (9, "Method '__str' looks like a misspelled dunder method"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_redundant_pass() -> Result<()> {
+    let code = r#"
+def empty():
+    pass
+
+
+def documented():
+    """Docs."""
+    pass
+
+
+if x:
+    do()
+    pass
+else:
+    pass
+
+try:
+    run()
+except Exception:
+    log()
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = RedundantPass.check(&LintContext::new(code, &ast))?;
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(8, 5, 8), (13, 5, 8), (21, 5, 8)]);
+    assert_eq!(diagnostics[0].message, "Redundant 'pass' statement");
+
+    Ok(())
+}
+
+#[test]
+fn test_inconsistent_returns() -> Result<()> {
+    let code = r#"
+def mixed(x):
+    if x:
+        return 1
+    return
+
+
+def falls_off(x):
+    if x:
+        return 1
+
+
+def consistent(x):
+    if x:
+        return 1
+    else:
+        raise ValueError(x)
+
+
+def no_values(x):
+    if x:
+        return
+    print(x)
+
+
+def generator(x):
+    if x:
+        return 1
+    yield x
+
+
+def outer():
+    def inner():
+        return
+    return inner
+
+
+def loops():
+    while True:
+        if done():
+            return 1
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = InconsistentReturns.check(&LintContext::new(code, &ast))?;
+
+    let lines: Vec<_> = diagnostics.iter().map(|d| d.line).collect();
+    assert_eq!(lines, vec![2, 8]);
+    assert_eq!(
+        diagnostics[0].message,
+        "Function 'mixed' has inconsistent return statements (some with value, some without)"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_type_annotations() -> Result<()> {
+    let code = r#"
+def annotated(x: int, *args: str, flag: bool = False, **kwargs: object) -> int:
+    return x
+
+
+def scale(x, factor: float) -> float:
+    return x * factor
+
+
+def _private(x):
+    return x
+
+
+class Shape:
+    def area(self, unit) -> float:
+        return 0.0
+
+    @classmethod
+    def build(cls):
+        return cls()
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = MissingTypeAnnotations.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (6, 11, "Parameter 'x' of 'scale' is missing a type annotation"),
+        (15, 20, "Parameter 'unit' of 'area' is missing a type annotation"),
+        (19, 5, "Function 'build' is missing a return type annotation"),
+    ]);
+
+    // Opt-in only
+    let mut config = rustlint::config::Config::default();
+    assert!(!rustlint::rules::get_rules(&config).iter().any(|r| r.code() == "ANN001"));
+    config.rules.require_annotations = true;
+    assert!(rustlint::rules::get_rules(&config).iter().any(|r| r.code() == "ANN001"));
+
+    Ok(())
+}
+
+#[test]
+fn test_attribute_outside_init() -> Result<()> {
+    let code = r#"
+class Cache:
+    def load(self, path):
+        self.data = read(path)
+
+
+class Initialized:
+    def __init__(self):
+        self.data = None
+
+    def load(self, path):
+        self.data = read(path)
+
+
+@dataclass
+class Point:
+    x: int = 0
+
+    def move(self, dx):
+        self.x += dx
+
+
+class Factory:
+    @classmethod
+    def build(cls):
+        cls.count = 1
+
+
+class Unpacked:
+    def load(this):
+        this.a, this.b = pair()
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = AttributeOutsideInit.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (2, "Class 'Cache' assigns instance attributes but has no __init__"),
+        (29, "Class 'Unpacked' assigns instance attributes but has no __init__"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_silent_except() -> Result<()> {
+    let code = r#"
+try:
+    run()
+except:
+    pass
+
+try:
+    run()
+except ValueError:
+    ...
+
+try:
+    run()
+except Exception:
+    logging.error("run failed")
+
+try:
+    run()
+except (KeyboardInterrupt, SystemExit):
+    pass
+
+try:
+    run()
+except (KeyboardInterrupt, OSError):
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = SilentExcept.check(&LintContext::new(code, &ast))?;
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(4, 1, 6), (9, 1, 6), (24, 1, 6)]);
+    assert_eq!(diagnostics[0].message, "Exception silently ignored; log or handle it");
+
+    Ok(())
+}
+
+#[test]
+fn test_is_literal() -> Result<()> {
+    let code = r#"
+if x is 5:
+    pass
+if x is not "foo":
+    pass
+if x is ():
+    pass
+if x is None or x is True or x is not False:
+    pass
+if x is y:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = IsLiteral.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (2, 4, 9, "Use '==' to compare with a literal, not 'is'"),
+        (4, 4, 17, "Use '!=' to compare with a literal, not 'is not'"),
+        (6, 4, 10, "Use '==' to compare with a literal, not 'is'"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_loop_variable_shadowing() -> Result<()> {
+    let code = r#"
+def scale(items, factor):
+    for factor in items:
+        print(factor)
+
+
+def grid(rows):
+    for i in rows:
+        for i in range(3):
+            print(i)
+    squares = [row * row for row in rows]
+    return [i for i in squares]
+
+
+def outer():
+    total = 0
+
+    def inner(values):
+        return sum(total for total in values)
+    return inner
+
+
+def fresh(rows):
+    for row in rows:
+        print(row)
+    for row in rows:
+        print(row)
+    return {key: value for key, value in rows}
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = LoopVariableShadowing.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (3, 9, "Loop variable 'factor' shadows an outer binding"),
+        (9, 13, "Loop variable 'i' shadows an outer binding"),
+        (19, 30, "Loop variable 'total' shadows an outer binding"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_fstring_nested_quotes() -> Result<()> {
+    use rustlint::config::PythonVersion;
+
+    let code = r#"
+label = f"{row["id"]}: {row['name']}"
+plain = f"{row['id']}" + "{row["id"]}"
+block = f"""{row["id"]}"""
+spec = f"{value:'^10}"
+# f"{row["id"]}"
+inner = f'{", ".join(names)}' + f'{row['id']}'
+"#;
+    // Nested same-quote f-strings don't parse before 3.12, so the rule works on the text
+    let ast = Vec::new();
+    let context = LintContext::new(code, &ast);
+
+    let diagnostics = FStringNestedQuotes::new(PythonVersion::new(3, 8)).check(&context)?;
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column)).collect();
+    assert_eq!(spans, vec![(2, 16), (7, 40)]);
+    assert_eq!(diagnostics[0].message, "Nested same-type quotes in f-string require Python 3.12+");
+
+    assert!(FStringNestedQuotes::new(PythonVersion::new(3, 12)).check(&context)?.is_empty());
+
+    // The target version is read from the config
+    let config: rustlint::config::Config = toml::from_str("target_version = \"3.12\"\n")?;
+    assert_eq!(config.target_version, PythonVersion::new(3, 12));
+    assert!(toml::from_str::<rustlint::config::Config>("target_version = \"three\"\n").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_suspicious_assign_target() -> Result<()> {
+    let code = r#"
+[][0] = 1
+{}["k"] = 1
+a, (1, 2).real = pair()
+{}["count"] += 1
+d["k"] = 1
+obj.attr = 2
+[first, second] = pair()
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = SuspiciousAssignTarget.check(&LintContext::new(code, &ast))?;
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(2, 1, 5), (3, 1, 7), (4, 4, 14), (5, 1, 11)]);
+    assert_eq!(diagnostics[0].message, "Assignment to a temporary object has no effect");
+
+    Ok(())
+}
+
+#[test]
+fn test_not_in_not_eq() -> Result<()> {
+    let code = r#"
+if not a == b:
+    pass
+if not key in mapping:
+    pass
+if not flag:
+    pass
+if not a != b or not (a < b) or not a == b == c:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = NotInNotEq.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (2, 4, 13, "Use 'a != b' instead of 'not a == b'"),
+        (4, 4, 21, "Use 'a not in b' instead of 'not a in b'"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_open_without_with() -> Result<()> {
+    let code = r#"
+f = open("x")
+log: IO = open("log.txt", "a")
+with open("x") as f:
+    data = f.read()
+with open("a") as src, open("b", "w") as dst:
+    dst.write(src.read())
+text = read("x")
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = OpenWithoutWith.check(&LintContext::new(code, &ast))?;
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(2, 5, 13), (3, 11, 30)]);
+    assert_eq!(diagnostics[0].message, "File opened without a context manager; use 'with open(...)'");
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_definition() -> Result<()> {
+    let code = r#"
+from typing import overload
+
+def foo():
+    return 1
+
+def foo():
+    return 2
+
+class Config:
+    @property
+    def name(self):
+        return self._name
+
+    @name.setter
+    def name(self, value):
+        self._name = value
+
+    def reset(self):
+        pass
+
+    async def reset(self):
+        pass
+
+class Config:
+    pass
+
+@overload
+def parse(value: int) -> int: ...
+@overload
+def parse(value: str) -> str: ...
+def parse(value):
+    return value
+
+if fast:
+    def helper(): pass
+else:
+    def helper(): pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = DuplicateDefinition.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (7, "Redefinition of function 'foo' (previous definition on line 4)"),
+        (22, "Redefinition of function 'reset' (previous definition on line 19)"),
+        (25, "Redefinition of class 'Config' (previous definition on line 10)"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicate_definition_reported_once() -> Result<()> {
+    let code = "def handler():\n    return 1\n\ndef handler():\n    return 2\n\nclass Model:\n    pass\n\nclass Model:\n    pass\n\nvalue = 1\ndef value():\n    return 3\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let context = LintContext::new(code, &ast);
+    let mut diagnostics = Vec::new();
+    for rule in get_default_rules() {
+        diagnostics.extend(rule.check(&context)?);
+    }
+
+    let redefinitions: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == "E0102" || d.code == "F811")
+        .map(|d| (d.line, d.code.as_str()))
+        .collect();
+    // A variable replaced by a function is still left to F811
+    assert_eq!(redefinitions, vec![(14, "F811"), (4, "E0102"), (10, "E0102")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_incompatible_comparison() -> Result<()> {
+    let code = r#"
+if "1" == 1:
+    pass
+if [] == () or b"x" != "x":
+    pass
+if x == 1 or 1 == 1.0 or True == 1 or "a" == "b" or value is None:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = IncompatibleComparison.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (2, 4, 11, "Comparison between str and int is always False"),
+        (4, 4, 11, "Comparison between list and tuple is always False"),
+        (4, 16, 26, "Comparison between bytes and str is always True"),
+    ]);
+
+    // Left to this rule rather than reported twice
+    assert!(ConstantComparison.check(&LintContext::new(code, &ast))?.iter().all(|d| d.line == 6));
+
+    Ok(())
+}
+
+#[test]
+fn test_return_in_finally() -> Result<()> {
+    let code = r#"
+def load(path):
+    try:
+        data = read(path)
+    finally:
+        return data
+
+def cleanup(handle):
+    try:
+        handle.write()
+    finally:
+        handle.close()
+
+for item in items:
+    try:
+        process(item)
+    finally:
+        if item.last:
+            break
+        for retry in range(3):
+            if flush():
+                break
+        continue
+
+try:
+    pass
+finally:
+    def callback():
+        return 1
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = ReturnInFinally.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (6, 9, 19, "'return' in a 'finally' block swallows exceptions"),
+        (19, 13, 17, "'break' in a 'finally' block swallows exceptions"),
+        (23, 9, 16, "'continue' in a 'finally' block swallows exceptions"),
+    ]);
+
+    Ok(())
+}
+
+#[test]
+fn test_ambiguous_name() -> Result<()> {
+    let code = r#"
+l = 1
+ll = 1
+def area(O, width):
+    return O * width
+for I, item in enumerate(items):
+    pass
+squares = [l * l for l in range(3)]
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let names = vec!["l".to_string(), "O".to_string(), "I".to_string()];
+    let diagnostics = AmbiguousName::new(names).check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![
+        (2, 1, "Ambiguous variable name 'l'"),
+        (4, 10, "Ambiguous variable name 'O'"),
+        (6, 5, "Ambiguous variable name 'I'"),
+        (8, 22, "Ambiguous variable name 'l'"),
+    ]);
+
+    // The set of names comes from the config
+    let config: rustlint::config::Config = toml::from_str("[rules]\nambiguous_names = [\"ll\"]\n")?;
+    let diagnostics = AmbiguousName::new(config.rules.ambiguous_names).check(&LintContext::new(code, &ast))?;
+    let lines: Vec<_> = diagnostics.iter().map(|d| d.line).collect();
+    assert_eq!(lines, vec![3]);
+
+    Ok(())
+}
+
+#[test]
+fn test_implicit_namespace_package() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("module.py");
+    let code = "import os\n\ndef load():\n    from . import helpers\n    from .models import User\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let context = LintContext::new(code, &ast).with_path(&path);
+
+    let diagnostics = ImplicitNamespacePackage.check(&context)?;
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(4, 5, 25)]);
+    assert_eq!(diagnostics[0].message, "Relative import used but no __init__.py in package directory");
+
+    // Absolute imports don't need a package
+    let absolute = "import os\nfrom os import path\n";
+    let absolute_ast = rustpython_parser::parser::parse_program(absolute, "<string>")?;
+    assert!(ImplicitNamespacePackage.check(&LintContext::new(absolute, &absolute_ast).with_path(&path))?.is_empty());
+
+    fs::write(dir.path().join("__init__.py"), "")?;
+    assert!(ImplicitNamespacePackage.check(&context)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_trailing_comma() -> Result<()> {
+    use rustlint::config::TrailingCommaStyle;
+
+    let code = r#"
+result = compute(
+    first,
+    second
+)
+items = [
+    1,
+    2,  # two
+]
+def handler(
+    request,
+    timeout: int = 30
+):
+    return fetch(request, timeout)
+pair = (
+    1,
+)
+total = sum(
+    x for x in items
+)
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let context = LintContext::new(code, &ast);
+    let spans = |style| -> Result<Vec<_>> {
+        let diagnostics = TrailingComma::new(style).check(&context)?;
+        Ok(diagnostics.iter().map(|d| (d.line, d.column, d.message.clone())).collect())
+    };
+
+    assert_eq!(spans(TrailingCommaStyle::Require)?, vec![
+        (4, 11, "Missing trailing comma".to_string()),
+        (12, 22, "Missing trailing comma".to_string()),
+    ]);
+    assert_eq!(spans(TrailingCommaStyle::Forbid)?, vec![
+        (8, 6, "Unexpected trailing comma".to_string()),
+    ]);
+    assert!(spans(TrailingCommaStyle::Off)?.is_empty());
+
+    // Single-line brackets are never checked
+    let code = "result = compute(first, second)\nitems = [1, 2,]\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    assert!(TrailingComma::new(TrailingCommaStyle::Require).check(&LintContext::new(code, &ast))?.is_empty());
+    assert!(TrailingComma::new(TrailingCommaStyle::Forbid).check(&LintContext::new(code, &ast))?.is_empty());
+
+    let config: rustlint::config::Config = toml::from_str("[rules]\ntrailing_comma = \"require\"\n")?;
+    assert_eq!(config.rules.trailing_comma, TrailingCommaStyle::Require);
+
+    Ok(())
+}
+
+#[test]
+fn test_float_equality() -> Result<()> {
+    let code = r#"
+if x == 0.1:
+    pass
+if total != -1.5 or ratio == 1e-3:
+    pass
+if x == 0 or x < 0.1 or 0.5 == 0.5:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = FloatEquality.check(&LintContext::new(code, &ast))?;
+
+    let spans: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column)).collect();
+    assert_eq!(spans, vec![(2, 4, 11), (4, 4, 16), (4, 21, 33)]);
+    assert_eq!(diagnostics[0].message, "Direct float equality comparison; consider math.isclose()");
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_imports_allowlist_and_noqa() -> Result<()> {
+    let code = "from .models import User\nimport os  # noqa: F401\nimport re  # noqa\nimport io  # noqa: E501\n";
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let rule = UnusedImports::new(&rustlint::config::Config::default().rules.allow_unused_in);
+
+    // Package facades re-export what they import
+    let init = PathBuf::from("pkg/__init__.py");
+    assert!(rule.check(&LintContext::new(code, &ast).with_path(&init))?.is_empty());
+    let dotted_init = PathBuf::from("./pkg/__init__.py");
+    assert!(rule.check(&LintContext::new(code, &ast).with_path(&dotted_init))?.is_empty());
+
+    // Elsewhere only the imports without a matching noqa are reported
+    let module = PathBuf::from("pkg/models.py");
+    let diagnostics = rule.check(&LintContext::new(code, &ast).with_path(&module))?;
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(1, "Unused import 'User'"), (4, "Unused import 'io'")]);
+
+    // A configured allowlist replaces the default one
+    let rule = UnusedImports::new(&["pkg/*.py".to_string()]);
+    assert!(rule.check(&LintContext::new(code, &ast).with_path(&module))?.is_empty());
+    let other_init = PathBuf::from("lib/__init__.py");
+    assert_eq!(rule.check(&LintContext::new(code, &ast).with_path(&other_init))?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_unused_exception_variable() -> Result<()> {
+    let code = r#"
+try:
+    run()
+except ValueError as e:
+    print("failed")
+try:
+    run()
+except Exception as error:
+    log.warning("failed: %s", str(error))
+except (KeyError, OSError) as _ignored:
+    pass
+except RuntimeError as exc:
+    callbacks.append(lambda: exc)
+except TypeError:
+    pass
+"#;
+    let ast = rustpython_parser::parser::parse_program(code, "<string>")?;
+    let diagnostics = UnusedExceptionVariable.check(&LintContext::new(code, &ast))?;
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| (d.line, d.column, d.end_column, d.message.as_str())).collect();
+    assert_eq!(messages, vec![(4, 22, 22, "Exception variable 'e' is never used")]);
+
+    Ok(())
+}